@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::io;
+use std::iter;
+use std::iter::FusedIterator;
+use std::mem;
+use std::path::Path;
+
+use super::error::ParentError;
+use super::imp;
+use super::BasePath;
+use super::BasePathBuf;
+use super::EnvProvider;
+use super::PathExt;
+use super::SystemEnv;
+
+#[derive(Clone, Debug)]
+enum State<'a> {
+    Path(&'a BasePath),
+    Error(ParentError),
+    Done,
+}
+
+/// An iterator over a [`BasePath`] and its ancestors.
+///
+/// This struct is created by [`BasePath::ancestors`]. See its documentation
+/// for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Ancestors<'a> {
+    state: State<'a>,
+    // Iterating from the back requires finding all ancestors, so they are
+    // only stored once that is necessary.
+    buffer: Option<VecDeque<Result<&'a BasePath, ParentError>>>,
+}
+
+impl<'a> Ancestors<'a> {
+    pub(super) fn new(path: &'a BasePath) -> Self {
+        Self {
+            state: State::Path(path),
+            buffer: None,
+        }
+    }
+
+    fn next_unbuffered(
+        &mut self,
+    ) -> Option<Result<&'a BasePath, ParentError>> {
+        match mem::replace(&mut self.state, State::Done) {
+            State::Path(path) => {
+                // Paths without a parent end iteration, even if they are not
+                // normalized.
+                if path.parent_unchecked().is_some() {
+                    self.state = match path.parent() {
+                        Ok(Some(parent)) => State::Path(parent),
+                        Ok(None) => State::Done,
+                        Err(error) => State::Error(error),
+                    };
+                }
+                Some(Ok(path))
+            }
+            State::Error(error) => Some(Err(error)),
+            State::Done => None,
+        }
+    }
+
+    fn buffer(&mut self) -> &mut VecDeque<Result<&'a BasePath, ParentError>> {
+        if self.buffer.is_none() {
+            self.buffer =
+                Some(iter::from_fn(|| self.next_unbuffered()).collect());
+        }
+        self.buffer.as_mut().expect("buffer was not initialized")
+    }
+}
+
+impl<'a> DoubleEndedIterator for Ancestors<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.buffer().pop_back()
+    }
+}
+
+impl FusedIterator for Ancestors<'_> {}
+
+impl<'a> Iterator for Ancestors<'a> {
+    type Item = Result<&'a BasePath, ParentError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(buffer) = &mut self.buffer {
+            buffer.pop_front()
+        } else {
+            self.next_unbuffered()
+        }
+    }
+}
+
+#[derive(Debug)]
+enum SearchState {
+    Start,
+    Path(BasePathBuf),
+    Visited(BasePathBuf),
+    Done,
+}
+
+/// An iterator over the ancestors of a [`BasePath`] that contain a marker
+/// file.
+///
+/// This struct is created by [`BasePath::ancestors_with`]. See its
+/// documentation for more information.
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct AncestorsWith<'a, M> {
+    path: &'a BasePath,
+    markers: &'a [M],
+    state: SearchState,
+    stop_paths: Vec<BasePathBuf>,
+    stop_at_filesystem_boundary: bool,
+}
+
+impl<'a, M> AncestorsWith<'a, M>
+where
+    M: AsRef<Path>,
+{
+    pub(super) fn new(path: &'a BasePath, markers: &'a [M]) -> Self {
+        Self {
+            path,
+            markers,
+            state: SearchState::Start,
+            stop_paths: Vec::new(),
+            stop_at_filesystem_boundary: false,
+        }
+    }
+
+    /// Stops iteration after visiting `path`, if it is an ancestor.
+    ///
+    /// This method can be called multiple times to stop at the first of
+    /// multiple directories. If `path` cannot be [normalized], it will be
+    /// ignored.
+    ///
+    /// [normalized]: PathExt::normalize
+    #[inline]
+    pub fn stop_at<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        if let Ok(path) = path.as_ref().normalize() {
+            self.stop_paths.push(path);
+        }
+        self
+    }
+
+    /// Stops iteration before leaving the file system that contains the
+    /// previously visited directory.
+    ///
+    /// This option prevents searching mount points, such as network shares,
+    /// that can be slow to access.
+    ///
+    /// # Implementation
+    ///
+    /// Currently, file systems are compared using the device ID on Unix and
+    /// the volume serial number on Windows. However, the implementation is
+    /// subject to change. This section is only informative.
+    #[inline]
+    pub fn stop_at_filesystem_boundary(mut self) -> Self {
+        self.stop_at_filesystem_boundary = true;
+        self
+    }
+
+    /// Equivalent to [`stop_at`] for the home directory of the current user.
+    ///
+    /// If the home directory is unknown, this method has no effect.
+    ///
+    /// [`stop_at`]: Self::stop_at
+    #[inline]
+    pub fn stop_at_home(self) -> Self {
+        self.stop_at_home_with(&SystemEnv)
+    }
+
+    /// Equivalent to [`stop_at_home`] but reads the home directory from
+    /// `env`.
+    ///
+    /// [`stop_at_home`]: Self::stop_at_home
+    #[inline]
+    pub fn stop_at_home_with<E>(self, env: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        if let Some(home) = env.home_dir() {
+            self.stop_at(home)
+        } else {
+            self
+        }
+    }
+
+    // Iteration ends if the file systems cannot be compared, since the
+    // boundary might otherwise be crossed.
+    fn next_path(&self, path: &BasePath) -> io::Result<SearchState> {
+        if self.stop_paths.iter().any(|x| x == path) {
+            return Ok(SearchState::Done);
+        }
+        // Normalized paths always have a parent unless they are a root.
+        let Some(parent) = path.parent_unchecked() else {
+            return Ok(SearchState::Done);
+        };
+        if self.stop_at_filesystem_boundary
+            && imp::device_id(path.as_path())?
+                != imp::device_id(parent.as_path())?
+        {
+            return Ok(SearchState::Done);
+        }
+        Ok(SearchState::Path(parent.to_owned()))
+    }
+
+    fn has_marker(&self, path: &BasePath) -> io::Result<bool> {
+        for marker in self.markers {
+            if path.join(marker).try_exists()? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl<M> FusedIterator for AncestorsWith<'_, M> where M: AsRef<Path> {}
+
+impl<M> Iterator for AncestorsWith<'_, M>
+where
+    M: AsRef<Path>,
+{
+    type Item = io::Result<BasePathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match mem::replace(&mut self.state, SearchState::Done) {
+                SearchState::Start => match self.path.normalize() {
+                    Ok(path) => self.state = SearchState::Path(path),
+                    Err(error) => return Some(Err(error)),
+                },
+                // Markers are checked before moving to the parent, so errors
+                // from either step do not hide the other.
+                SearchState::Path(path) => {
+                    let result = match self.has_marker(&path) {
+                        Ok(true) => Some(Ok(path.clone())),
+                        Ok(false) => None,
+                        Err(error) => Some(Err(error)),
+                    };
+                    self.state = SearchState::Visited(path);
+                    if result.is_some() {
+                        return result;
+                    }
+                }
+                SearchState::Visited(path) => match self.next_path(&path) {
+                    Ok(state) => self.state = state,
+                    Err(error) => return Some(Err(error)),
+                },
+                SearchState::Done => return None,
+            }
+        }
+    }
+}
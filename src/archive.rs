@@ -0,0 +1,61 @@
+use std::ffi::OsStr;
+
+use super::error::EntryError;
+use super::lexical;
+use super::BasePath;
+use super::BasePathBuf;
+
+#[cfg(unix)]
+fn os_str(string: &[u8]) -> Result<&OsStr, EntryError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    Ok(OsStr::from_bytes(string))
+}
+
+#[cfg(not(unix))]
+fn os_str(string: &[u8]) -> Result<&OsStr, EntryError> {
+    std::str::from_utf8(string)
+        .map(OsStr::new)
+        .map_err(|_| EntryError::InvalidEncoding)
+}
+
+pub(super) fn join_entry(
+    base: &BasePath,
+    name: &[u8],
+) -> Result<BasePathBuf, EntryError> {
+    // Archives can be created on any platform, so both separators must be
+    // handled.
+    match name {
+        [x, y, ..]
+            if lexical::is_separator(*x) && lexical::is_separator(*y) =>
+        {
+            return Err(EntryError::UncPrefix);
+        }
+        [x, ..] if lexical::is_separator(*x) => {
+            return Err(EntryError::Absolute);
+        }
+        _ => {}
+    }
+
+    let mut components = Vec::new();
+    for component in name.split(|&x| lexical::is_separator(x)) {
+        match component {
+            b"" | b"." => {}
+            b".." => {
+                if components.pop().is_none() {
+                    return Err(EntryError::EscapesBase);
+                }
+            }
+            [drive, b':', ..] if drive.is_ascii_alphabetic() => {
+                return Err(EntryError::DrivePrefix);
+            }
+            _ => components.push(os_str(component)?),
+        }
+    }
+
+    let mut path = base.to_owned();
+    for component in components {
+        path.push(component);
+    }
+    Ok(path)
+}
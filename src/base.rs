@@ -14,12 +14,76 @@ use std::path::Component;
 use std::path::Components;
 use std::path::Path;
 use std::path::PathBuf;
-
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::archive;
+use super::error::EntryError;
+use super::error::ExpandError;
+use super::error::FileUrlError;
+use super::error::InvalidFileNameError;
+#[cfg(feature = "localization")]
+use super::error::LocalizeError;
 use super::error::MissingPrefixBufError;
 use super::error::MissingPrefixError;
 use super::error::ParentError;
+use super::error::ValidationError;
+use super::expand;
+use super::extensions;
 use super::imp;
+use super::lexical;
+#[cfg(feature = "localization")]
+use super::localize::Localizer;
+#[cfg(feature = "localization")]
+use super::localized;
+use super::logical;
+#[cfg(target_os = "linux")]
+use super::mount;
+use super::prefix;
+use super::sanitize;
+use super::translate;
+use super::url;
+use super::validate;
+use super::verbatim;
+use super::Ancestors;
+use super::AncestorsWith;
+use super::EnvProvider;
+use super::Extensions;
+use super::FileId;
+#[cfg(feature = "localization")]
+use super::Localized;
+#[cfg(feature = "localization")]
+use super::LocalizedComponents;
+#[cfg(target_os = "linux")]
+use super::MountInfo;
 use super::PathExt;
+use super::Platform;
+use super::PosixStyle;
+use super::PosixTranslator;
+use super::PrefixInfo;
+use super::RelPath;
+use super::SanitizeOptions;
+use super::ShellSyntax;
+use super::SystemEnv;
+
+fn cow_os_str_into_base_path(path: Cow<'_, OsStr>) -> Cow<'_, BasePath> {
+    debug_assert!(imp::is_base(Path::new(&path)));
+
+    match path {
+        Cow::Borrowed(path) => Cow::Borrowed(BasePath::from_inner(path)),
+        Cow::Owned(path) => Cow::Owned(BasePathBuf(path.into())),
+    }
+}
+
+fn check_file_name(file_name: &OsStr) -> Result<(), InvalidFileNameError> {
+    // Separators, prefixes, and special components all prevent the value
+    // from being returned as the name of the path that it forms.
+    if Path::new(file_name).file_name() == Some(file_name) {
+        Ok(())
+    } else {
+        Err(InvalidFileNameError(()))
+    }
+}
 
 fn cow_path_into_base_path(path: Cow<'_, Path>) -> Cow<'_, BasePath> {
     debug_assert!(imp::is_base(&path));
@@ -149,6 +213,96 @@ impl BasePath {
         Path::new(&self.0)
     }
 
+    /// Returns an iterator over this path and its ancestors.
+    ///
+    /// This method is similar to [`Path::ancestors`], but it uses [`parent`]
+    /// to find each ancestor. Therefore, if a path with a trailing `..`
+    /// component is reached, an error is returned instead of its parent, and
+    /// iteration ends. To stop cleanly instead, use
+    /// [`map_while(Result::ok)`][map_while].
+    ///
+    /// The iterator is double-ended, so ancestors can also be visited from
+    /// the root first using [`Iterator::rev`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// # use normpath::error::ParentError;
+    /// use normpath::BasePath;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = BasePath::try_new(r"X:\foo\bar").unwrap();
+    ///     assert_eq!(
+    ///         [Path::new(r"X:\"), Path::new(r"X:\foo"), Path::new(r"X:\foo\bar")],
+    ///         *path.ancestors().rev().collect::<Result<Vec<_>, _>>()?,
+    ///     );
+    ///
+    ///     let path = BasePath::try_new(r"X:\foo\..\bar").unwrap();
+    ///     let mut ancestors = path.ancestors();
+    ///     assert_eq!(Path::new(r"X:\foo\..\bar"), ancestors.next().unwrap()?);
+    ///     assert_eq!(Path::new(r"X:\foo\.."), ancestors.next().unwrap()?);
+    ///     assert!(ancestors.next().unwrap().is_err());
+    ///     assert!(ancestors.next().is_none());
+    /// }
+    /// #
+    /// # Ok::<_, ParentError>(())
+    /// ```
+    ///
+    /// [map_while]: Iterator::map_while
+    /// [`parent`]: Self::parent
+    #[inline]
+    pub fn ancestors(&self) -> Ancestors<'_> {
+        Ancestors::new(self)
+    }
+
+    /// Returns an iterator over the ancestors of this path that contain any
+    /// of the given marker files or directories.
+    ///
+    /// The path is [normalized] first, and then the path and its ancestors
+    /// are visited from nearest to farthest. A directory is yielded if any of
+    /// its children named by `markers` exists. Options can be used to stop
+    /// iteration early, such as [at the home directory].
+    ///
+    /// For only the nearest match, use [`find_ancestor_with`].
+    ///
+    /// # Errors
+    ///
+    /// Yields an error and ends iteration if normalization fails, or if file
+    /// systems cannot be compared for [`stop_at_filesystem_boundary`]. Errors
+    /// accessing markers are also yielded, but iteration continues with the
+    /// next ancestor.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use normpath::BasePathBuf;
+    ///
+    /// let path = BasePathBuf::new(".")?;
+    /// for config in path.ancestors_with(&[".editorconfig"]).stop_at_home() {
+    ///     let config = config?.join(".editorconfig");
+    ///     println!("{}", config.as_path().display());
+    /// }
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`stop_at_filesystem_boundary`]: AncestorsWith::stop_at_filesystem_boundary
+    /// [at the home directory]: AncestorsWith::stop_at_home
+    /// [`find_ancestor_with`]: Self::find_ancestor_with
+    /// [normalized]: Self::normalize
+    #[inline]
+    pub fn ancestors_with<'a, M>(
+        &'a self,
+        markers: &'a [M],
+    ) -> AncestorsWith<'a, M>
+    where
+        M: AsRef<Path>,
+    {
+        AncestorsWith::new(self, markers)
+    }
+
     /// Equivalent to [`Path::canonicalize`].
     #[inline]
     pub fn canonicalize(&self) -> io::Result<BasePathBuf> {
@@ -194,6 +348,75 @@ impl BasePath {
         self.as_path().extension()
     }
 
+    /// Returns an iterator over the extensions of the file name, starting
+    /// with the outermost.
+    ///
+    /// Extensions are the parts of the [file name] after the [prefix], which
+    /// are separated by periods. Therefore, the first extension is the same
+    /// as [`extension`], but a leading period never starts an extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let root = if cfg!(windows) { r"X:\" } else { "/" };
+    /// let root = BasePath::try_new(root).unwrap();
+    ///
+    /// let path = root.join("archive.tar.gz");
+    /// assert_eq!(
+    ///     [OsStr::new("gz"), OsStr::new("tar")],
+    ///     *path.extensions().collect::<Vec<_>>(),
+    /// );
+    ///
+    /// let path = root.join(".bashrc");
+    /// assert_eq!(None, path.extensions().next());
+    /// ```
+    ///
+    /// [`extension`]: Self::extension
+    /// [file name]: Self::file_name
+    /// [prefix]: Self::file_prefix
+    #[inline]
+    pub fn extensions(&self) -> Extensions<'_> {
+        Extensions::new(self.file_name())
+    }
+
+    /// Returns an identifier for the file at this path.
+    ///
+    /// Symbolic links are followed, so the identifier is for the file that
+    /// they refer to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or cannot be accessed, or
+    /// if file identifiers are not supported on this platform.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::collections::HashSet;
+    /// use std::io;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let mut files = HashSet::new();
+    /// for entry in Path::new("foo").read_dir()? {
+    ///     let path = BasePath::new(entry?.path())?;
+    ///     if !files.insert(path.file_id()?) {
+    ///         println!("duplicate: {}", path.as_path().display());
+    ///     }
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    #[inline]
+    pub fn file_id(&self) -> io::Result<FileId> {
+        imp::file_id(self.as_path())
+    }
+
     /// Equivalent to [`Path::file_name`].
     #[inline]
     #[must_use]
@@ -201,6 +424,43 @@ impl BasePath {
         self.as_path().file_name()
     }
 
+    /// Returns the part of the file name before its first extension.
+    ///
+    /// This method is similar to [`file_stem`], but it removes all
+    /// [extensions] instead of only the last. A leading period is kept, so the
+    /// prefix of `.bashrc` is the entire name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let root = if cfg!(windows) { r"X:\" } else { "/" };
+    /// let root = BasePath::try_new(root).unwrap();
+    ///
+    /// let path = root.join("foo.d.ts");
+    /// assert_eq!(Some(OsStr::new("foo")), path.file_prefix());
+    ///
+    /// let path = root.join(".config.toml");
+    /// assert_eq!(Some(OsStr::new(".config")), path.file_prefix());
+    ///
+    /// assert_eq!(None, root.file_prefix());
+    /// ```
+    ///
+    /// [extensions]: Self::extensions
+    /// [`file_stem`]: Self::file_stem
+    #[inline]
+    #[must_use]
+    pub fn file_prefix(&self) -> Option<&OsStr> {
+        self.file_name().map(|x| {
+            lexical::os_str(
+                extensions::split_file_prefix(x.as_encoded_bytes()).0,
+            )
+        })
+    }
+
     /// Equivalent to [`Path::file_stem`].
     #[inline]
     #[must_use]
@@ -208,6 +468,79 @@ impl BasePath {
         self.as_path().file_stem()
     }
 
+    /// Returns the nearest ancestor of this path that contains any of the
+    /// given marker files or directories.
+    ///
+    /// This method is equivalent to taking the first item of
+    /// [`ancestors_with`], which also has options to limit the search. For
+    /// example, `path.ancestors_with(markers).stop_at_home().next()` finds
+    /// the nearest match below the home directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this path cannot be [normalized] or if an error
+    /// occurs before a match is found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use normpath::BasePathBuf;
+    ///
+    /// let path = BasePathBuf::new(".")?;
+    /// if let Some(root) = path.find_ancestor_with(&["Cargo.toml", ".git"])? {
+    ///     println!("{}", root.as_path().display());
+    /// }
+    /// #
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    ///
+    /// [`ancestors_with`]: Self::ancestors_with
+    /// [normalized]: Self::normalize
+    #[inline]
+    pub fn find_ancestor_with<M>(
+        &self,
+        markers: &[M],
+    ) -> io::Result<Option<BasePathBuf>>
+    where
+        M: AsRef<Path>,
+    {
+        self.ancestors_with(markers).next().transpose()
+    }
+
+    /// Returns [`true`] if the file name ends with the given extension,
+    /// ignoring ASCII case.
+    ///
+    /// The extension can be compound, such as `tar.gz`, but it must consist
+    /// of whole [extensions]. It should not start with a period.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use normpath::BasePath;
+    ///
+    /// let root = if cfg!(windows) { r"X:\" } else { "/" };
+    /// let path = BasePath::try_new(root).unwrap().join("archive.TAR.GZ");
+    /// assert!(path.has_extension_ci("gz"));
+    /// assert!(path.has_extension_ci("tar.gz"));
+    /// assert!(!path.has_extension_ci("ar.gz"));
+    /// assert!(!path.has_extension_ci("archive.tar.gz"));
+    /// ```
+    ///
+    /// [extensions]: Self::extensions
+    #[inline]
+    #[must_use]
+    pub fn has_extension_ci<S>(&self, extension: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        self.file_name().is_some_and(|x| {
+            extensions::has_extension_ci(
+                x.as_encoded_bytes(),
+                extension.as_ref().as_encoded_bytes(),
+            )
+        })
+    }
+
     /// Equivalent to [`Path::has_root`].
     #[inline]
     #[must_use]
@@ -215,6 +548,16 @@ impl BasePath {
         self.as_path().has_root()
     }
 
+    /// Converts a boxed base path into an owned path without copying or
+    /// allocating.
+    #[inline]
+    #[must_use]
+    pub fn into_base_path_buf(self: Box<Self>) -> BasePathBuf {
+        let path = Box::into_raw(self) as *mut Path;
+        // SAFETY: This struct has the same layout as [Path].
+        BasePathBuf(unsafe { Box::from_raw(path) }.into_path_buf())
+    }
+
     /// Equivalent to [`Path::is_absolute`].
     #[inline]
     #[must_use]
@@ -236,6 +579,12 @@ impl BasePath {
         self.as_path().is_file()
     }
 
+    /// Equivalent to [`PathExt::is_hidden`].
+    #[inline]
+    pub fn is_hidden(&self) -> io::Result<bool> {
+        self.as_path().is_hidden()
+    }
+
     /// Equivalent to [`Path::is_relative`].
     #[inline]
     #[must_use]
@@ -289,84 +638,98 @@ impl BasePath {
         base
     }
 
-    /// Equivalent to [`PathExt::localize_name`].
-    #[cfg(feature = "localization")]
-    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
-    #[inline]
-    #[must_use]
-    pub fn localize_name(&self) -> Cow<'_, OsStr> {
-        self.as_path().localize_name()
-    }
-
-    /// Equivalent to [`Path::metadata`].
-    #[inline]
-    pub fn metadata(&self) -> io::Result<Metadata> {
-        self.as_path().metadata()
-    }
-
-    /// Equivalent to [`PathExt::normalize`].
-    #[inline]
-    pub fn normalize(&self) -> io::Result<BasePathBuf> {
-        self.as_path().normalize()
-    }
-
-    /// Equivalent to [`PathExt::normalize_virtually`].
-    #[cfg(any(doc, windows))]
-    #[cfg_attr(normpath_docs_rs, doc(cfg(windows)))]
-    #[inline]
-    pub fn normalize_virtually(&self) -> io::Result<BasePathBuf> {
-        self.as_path().normalize_virtually()
-    }
-
-    fn check_parent(&self) -> Result<(), ParentError> {
-        self.components()
-            .next_back()
-            .filter(|x| matches!(x, Component::Normal(_) | Component::RootDir))
-            .map(|_| ())
-            .ok_or(ParentError(()))
-    }
-
-    /// Returns this path without its last component.
+    /// Joins the name of an entry from an untrusted archive to this path.
     ///
-    /// Returns `Ok(None)` if the last component is [`Component::RootDir`].
+    /// Unlike [`join`], this method guarantees that the result is contained in
+    /// this path, which prevents [Zip Slip] vulnerabilities. Both `/` and `\`
+    /// are treated as separators on every platform, since archives can be
+    /// created on any platform. `.` components are removed, and `..`
+    /// components remove the previous component.
     ///
-    /// You should usually only call this method on [normalized] paths. They
-    /// will prevent an unexpected path from being returned due to symlinks,
-    /// and some `.` and `..` components will be normalized.
+    /// The file system is never accessed, so symbolic links within this path
+    /// can still lead outside of it.
     ///
     /// # Errors
     ///
-    /// Returns an error if the last component is not [`Component::Normal`] or
-    /// [`Component::RootDir`]. To ignore this error, use [`parent_unchecked`].
+    /// Returns an error if `name` is absolute, has a prefix, or has a `..`
+    /// component that would remove a component of this path. The error
+    /// describes the reason that the name was rejected.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::path::Path;
     ///
-    /// # use normpath::error::ParentError;
+    /// use normpath::error::EntryError;
     /// use normpath::BasePath;
     ///
-    /// if cfg!(windows) {
-    ///     assert_eq!(
-    ///         Path::new(r"X:\foo"),
-    ///         BasePath::try_new(r"X:\foo\bar").unwrap().parent()?.unwrap(),
-    ///     );
-    /// }
-    /// #
-    /// # Ok::<_, ParentError>(())
+    /// let base = if cfg!(windows) { r"X:\out" } else { "/out" };
+    /// let base = BasePath::try_new(base).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Path::new(base).join("foo").join("baz"),
+    ///     base.join_archive_entry("foo/bar/../baz").unwrap(),
+    /// );
+    ///
+    /// assert_eq!(Err(EntryError::Absolute), base.join_archive_entry("/etc/passwd"));
+    /// assert_eq!(Err(EntryError::DrivePrefix), base.join_archive_entry(r"C:\x"));
+    /// assert_eq!(Err(EntryError::EscapesBase), base.join_archive_entry(r"a\..\..\b"));
     /// ```
     ///
-    /// [normalized]: Self::normalize
-    /// [`parent_unchecked`]: Self::parent_unchecked
+    /// [`join`]: Self::join
+    /// [Zip Slip]: https://github.com/snyk/zip-slip-vulnerability
     #[inline]
-    pub fn parent(&self) -> Result<Option<&Self>, ParentError> {
-        self.check_parent().map(|()| self.parent_unchecked())
+    pub fn join_archive_entry<N>(
+        &self,
+        name: N,
+    ) -> Result<BasePathBuf, EntryError>
+    where
+        N: AsRef<[u8]>,
+    {
+        archive::join_entry(self, name.as_ref())
     }
 
-    /// Equivalent to [`Path::parent`].
+    /// Joins a relative path to this path.
     ///
-    /// It is usually better to use [`parent`].
+    /// Unlike [`join`], the result is guaranteed to be this path or one of its
+    /// descendants, since [`RelPath`] cannot contain a prefix, a root, or
+    /// `..` components. If `path` is empty, the result is equal to this path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    /// use normpath::RelPath;
+    ///
+    /// let base = if cfg!(windows) { r"X:\foo" } else { "/foo" };
+    /// let base = BasePath::try_new(base).unwrap();
+    ///
+    /// let path = RelPath::try_new("bar").unwrap();
+    /// assert_eq!(Path::new(base).join("bar"), base.join_rel(path));
+    ///
+    /// let path = RelPath::try_new("").unwrap();
+    /// assert_eq!(base, base.join_rel(path));
+    /// ```
+    ///
+    /// [`join`]: Self::join
+    #[inline]
+    #[must_use]
+    pub fn join_rel(&self, path: &RelPath) -> BasePathBuf {
+        let mut base = self.to_owned();
+        if !path.is_empty() {
+            base.push(path);
+        }
+        base
+    }
+
+    /// Joins a [sanitized] version of an untrusted name to this path.
+    ///
+    /// The result is always a direct child of this path, even if `name`
+    /// contains separators, is `..`, or would be interpreted as a prefix.
+    /// On Windows, names are always sanitized for Windows, in addition to the
+    /// platform configured by `options`.
     ///
     /// # Examples
     ///
@@ -374,199 +737,1640 @@ impl BasePath {
     /// use std::path::Path;
     ///
     /// use normpath::BasePath;
+    /// use normpath::SanitizeOptions;
     ///
     /// if cfg!(windows) {
+    ///     let path = BasePath::try_new(r"X:\uploads").unwrap();
     ///     assert_eq!(
-    ///         Path::new(r"X:\foo"),
-    ///         BasePath::try_new(r"X:\foo\..")
-    ///             .unwrap()
-    ///             .parent_unchecked()
-    ///             .unwrap(),
+    ///         Path::new(r"X:\uploads\.._C__secret.txt"),
+    ///         path.join_sanitized(r"..\C:\secret.txt", SanitizeOptions::new()),
+    ///     );
+    /// } else {
+    ///     let path = BasePath::try_new("/uploads").unwrap();
+    ///     assert_eq!(
+    ///         Path::new("/uploads/.._etc_passwd"),
+    ///         path.join_sanitized("../etc/passwd", SanitizeOptions::new()),
     ///     );
     /// }
     /// ```
     ///
-    /// [`parent`]: Self::parent
+    /// [sanitized]: super::sanitize_file_name
     #[inline]
     #[must_use]
-    pub fn parent_unchecked(&self) -> Option<&Self> {
-        self.as_path()
-            .parent()
-            .map(|x| Self::from_inner(x.as_os_str()))
+    pub fn join_sanitized<P>(
+        &self,
+        name: P,
+        options: SanitizeOptions,
+    ) -> BasePathBuf
+    where
+        P: AsRef<OsStr>,
+    {
+        let name = sanitize::sanitize(name.as_ref(), &options, cfg!(windows));
+        let path = self.join(&name);
+        debug_assert_eq!(Some(&*name), path.file_name());
+        path
     }
 
-    /// Equivalent to [`Path::read_dir`].
+    /// Equivalent to [`PathExt::localize_name`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
     #[inline]
-    pub fn read_dir(&self) -> io::Result<ReadDir> {
-        self.as_path().read_dir()
+    #[must_use]
+    pub fn localize_name(&self) -> Cow<'_, OsStr> {
+        self.as_path().localize_name()
     }
 
-    /// Equivalent to [`Path::read_link`].
+    /// Equivalent to [`PathExt::localize_name_in`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
     #[inline]
-    pub fn read_link(&self) -> io::Result<PathBuf> {
-        self.as_path().read_link()
+    #[must_use]
+    pub fn localize_name_in(&self, locale: &str) -> Cow<'_, OsStr> {
+        self.as_path().localize_name_in(locale)
     }
 
-    /// Equivalent to [`PathExt::shorten`].
+    /// Equivalent to [`PathExt::localize_name_in_with`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
     #[inline]
-    pub fn shorten(&self) -> io::Result<Cow<'_, Self>> {
-        self.as_path().shorten().map(cow_path_into_base_path)
+    #[must_use]
+    pub fn localize_name_in_with<E>(
+        &self,
+        locale: &str,
+        env: &E,
+    ) -> Cow<'_, OsStr>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        self.as_path().localize_name_in_with(locale, env)
     }
 
-    /// Equivalent to [`Path::starts_with`].
+    /// Equivalent to [`PathExt::localize_name_with`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
     #[inline]
     #[must_use]
-    pub fn starts_with<P>(&self, base: P) -> bool
+    pub fn localize_name_with<E>(&self, env: &E) -> Cow<'_, OsStr>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        self.as_path().localize_name_with(env)
+    }
+
+    /// Returns an iterator over the components of this path and their
+    /// localized names.
+    ///
+    /// Each [normal] component is localized like [`localize_name`] would
+    /// localize the path ending with it. Other components are returned
+    /// unchanged, so paths with `..` components do not cause a panic.
+    /// Localization data, such as configuration files, is only read once for
+    /// the iterator.
+    ///
+    /// The returned names should only be used for display to users, such as
+    /// in breadcrumbs. To display the entire path, use [`localize_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Component;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let path = if cfg!(windows) { r"X:\foo\bar" } else { "/foo/bar" };
+    /// let path = BasePath::try_new(path).unwrap();
+    /// for (component, name) in path.localize_components() {
+    ///     if let Component::Normal(component) = component {
+    ///         println!("{}: {}", component.display(), name.display());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`localize_name`]: Self::localize_name
+    /// [`localize_path`]: Self::localize_path
+    /// [normal]: Component::Normal
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    pub fn localize_components(&self) -> LocalizedComponents<'_> {
+        self.localize_components_with(&SystemEnv)
+    }
+
+    /// Equivalent to [`localize_components`] but reads environment variables
+    /// from `env`.
+    ///
+    /// For more information, see [`PathExt::localize_name_with`].
+    ///
+    /// [`localize_components`]: Self::localize_components
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    pub fn localize_components_with<E>(
+        &self,
+        env: &E,
+    ) -> LocalizedComponents<'_>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        LocalizedComponents::new(self, env)
+    }
+
+    /// Returns this path with each component replaced by its localized name.
+    ///
+    /// Components are localized using [`localize_components`] and joined
+    /// using the [main separator]. The result should only be used for display
+    /// to users.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use normpath::BasePath;
+    ///
+    /// let path = if cfg!(windows) { r"X:\foo\bar" } else { "/foo/bar" };
+    /// let path = BasePath::try_new(path).unwrap();
+    /// println!("{}", path.localize_path().display());
+    /// ```
+    ///
+    /// [`localize_components`]: Self::localize_components
+    /// [main separator]: ::std::path::MAIN_SEPARATOR
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    #[must_use]
+    pub fn localize_path(&self) -> OsString {
+        self.localize_path_with(&SystemEnv)
+    }
+
+    /// Equivalent to [`localize_path`] but reads environment variables from
+    /// `env`.
+    ///
+    /// For more information, see [`PathExt::localize_name_with`].
+    ///
+    /// [`localize_path`]: Self::localize_path
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    #[must_use]
+    pub fn localize_path_with<E>(&self, env: &E) -> OsString
+    where
+        E: EnvProvider + ?Sized,
+    {
+        localized::localize_path(self.localize_components_with(env))
+    }
+
+    /// Equivalent to [`PathExt::localize_type_name`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    #[must_use]
+    pub fn localize_type_name(&self) -> Option<OsString> {
+        self.as_path().localize_type_name()
+    }
+
+    /// Equivalent to [`PathExt::localize_type_name_with`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    #[must_use]
+    pub fn localize_type_name_with<E>(&self, env: &E) -> Option<OsString>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        self.as_path().localize_type_name_with(env)
+    }
+
+    /// Equivalent to [`Path::metadata`].
+    #[inline]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        self.as_path().metadata()
+    }
+
+    /// Returns information about the mounted file system containing this
+    /// path.
+    ///
+    /// The path is [normalized], so it must exist, and symbolic links are
+    /// followed. The mount with the longest mount point containing the
+    /// normalized path is then read from "/proc/self/mountinfo".
+    ///
+    /// This information can be used to group paths by file system or to
+    /// avoid caching information about paths on network file systems, which
+    /// might be modified by other computers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path cannot be normalized, if
+    /// "/proc/self/mountinfo" cannot be read or is invalid, or if no mount
+    /// contains the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let info = BasePath::new(Path::new("/"))?.mount_info()?;
+    /// assert_eq!(Path::new("/"), info.mount_point());
+    /// println!("{}", info.fs_type());
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [normalized]: Self::normalize
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(target_os = "linux")))]
+    #[inline]
+    pub fn mount_info(&self) -> io::Result<MountInfo> {
+        self.mount_info_from(mount::MOUNTINFO)
+    }
+
+    /// Equivalent to [`mount_info`] but reads mounts from the file at
+    /// `mountinfo`, which must use the format of "/proc/self/mountinfo".
+    ///
+    /// [`mount_info`]: Self::mount_info
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(target_os = "linux")))]
+    #[inline]
+    pub fn mount_info_from<P>(&self, mountinfo: P) -> io::Result<MountInfo>
     where
         P: AsRef<Path>,
     {
-        self.as_path().starts_with(base)
+        mount::mount_info(self, mountinfo.as_ref())
     }
 
-    /// Equivalent to [`Path::symlink_metadata`].
+    /// Equivalent to [`PathExt::normalize`].
     #[inline]
-    pub fn symlink_metadata(&self) -> io::Result<Metadata> {
-        self.as_path().symlink_metadata()
+    pub fn normalize(&self) -> io::Result<BasePathBuf> {
+        self.as_path().normalize()
     }
 
-    /// Equivalent to [`Path::try_exists`].
+    /// Equivalent to [`PathExt::normalize_virtually`].
+    #[cfg(any(doc, windows))]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(windows)))]
     #[inline]
-    pub fn try_exists(&self) -> io::Result<bool> {
-        self.as_path().try_exists()
+    pub fn normalize_virtually(&self) -> io::Result<BasePathBuf> {
+        self.as_path().normalize_virtually()
     }
-}
 
-impl AsRef<OsStr> for BasePath {
+    fn check_parent(&self) -> Result<(), ParentError> {
+        self.components()
+            .next_back()
+            .filter(|x| matches!(x, Component::Normal(_) | Component::RootDir))
+            .map(|_| ())
+            .ok_or(ParentError(()))
+    }
+
+    /// Returns this path without its last component.
+    ///
+    /// Returns `Ok(None)` if the last component is [`Component::RootDir`].
+    ///
+    /// You should usually only call this method on [normalized] paths. They
+    /// will prevent an unexpected path from being returned due to symlinks,
+    /// and some `.` and `..` components will be normalized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the last component is not [`Component::Normal`] or
+    /// [`Component::RootDir`]. To ignore this error, use [`parent_unchecked`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// # use normpath::error::ParentError;
+    /// use normpath::BasePath;
+    ///
+    /// if cfg!(windows) {
+    ///     assert_eq!(
+    ///         Path::new(r"X:\foo"),
+    ///         BasePath::try_new(r"X:\foo\bar").unwrap().parent()?.unwrap(),
+    ///     );
+    /// }
+    /// #
+    /// # Ok::<_, ParentError>(())
+    /// ```
+    ///
+    /// [normalized]: Self::normalize
+    /// [`parent_unchecked`]: Self::parent_unchecked
     #[inline]
-    fn as_ref(&self) -> &OsStr {
-        &self.0
+    pub fn parent(&self) -> Result<Option<&Self>, ParentError> {
+        self.check_parent().map(|()| self.parent_unchecked())
     }
-}
 
-impl AsRef<Path> for BasePath {
+    /// Equivalent to [`Path::parent`].
+    ///
+    /// It is usually better to use [`parent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// if cfg!(windows) {
+    ///     assert_eq!(
+    ///         Path::new(r"X:\foo"),
+    ///         BasePath::try_new(r"X:\foo\..")
+    ///             .unwrap()
+    ///             .parent_unchecked()
+    ///             .unwrap(),
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// [`parent`]: Self::parent
+    #[inline]
+    #[must_use]
+    pub fn parent_unchecked(&self) -> Option<&Self> {
+        self.as_path()
+            .parent()
+            .map(|x| Self::from_inner(x.as_os_str()))
+    }
+
+    /// Returns the prefix of this path.
+    ///
+    /// Equivalent to calling [`split_prefix`] and ignoring the remaining
+    /// path.
+    ///
+    /// [`split_prefix`]: Self::split_prefix
+    #[inline]
+    #[must_use]
+    pub fn prefix_info(&self) -> Option<PrefixInfo<'_>> {
+        self.split_prefix().map(|(prefix, _)| prefix)
+    }
+
+    /// Equivalent to [`Path::read_dir`].
+    #[inline]
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.as_path().read_dir()
+    }
+
+    /// Equivalent to [`Path::read_link`].
+    #[inline]
+    pub fn read_link(&self) -> io::Result<PathBuf> {
+        self.as_path().read_link()
+    }
+
+    /// Finds the child of this directory that is displayed to users as
+    /// `display_name`.
+    ///
+    /// Each child is localized using [`localize_name`] and compared to
+    /// `display_name`. Names are compared ignoring case on platforms where
+    /// file systems usually ignore it, which are currently MacOS and Windows.
+    /// Exact matches are preferred, but if multiple children match otherwise,
+    /// the result is unspecified.
+    ///
+    /// Each call reads and localizes every child of this directory. To search
+    /// the same directories repeatedly, use [`LocalizedNameCache`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the children of this directory cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let home = BasePath::new(Path::new("/home/foo"))?;
+    /// if let Some(path) = home.resolve_localized_child("Dokumente")? {
+    ///     println!("{}", path.as_path().display());
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [`localize_name`]: Self::localize_name
+    /// [`LocalizedNameCache`]: super::LocalizedNameCache
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    pub fn resolve_localized_child<S>(
+        &self,
+        display_name: S,
+    ) -> io::Result<Option<BasePathBuf>>
+    where
+        S: AsRef<OsStr>,
+    {
+        self.resolve_localized_child_with(display_name, &SystemEnv)
+    }
+
+    /// Equivalent to [`resolve_localized_child`] but reads environment
+    /// variables from `env`.
+    ///
+    /// For more information, see [`PathExt::localize_name_with`].
+    ///
+    /// [`resolve_localized_child`]: Self::resolve_localized_child
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    pub fn resolve_localized_child_with<S, E>(
+        &self,
+        display_name: S,
+        env: &E,
+    ) -> io::Result<Option<BasePathBuf>>
+    where
+        S: AsRef<OsStr>,
+        E: EnvProvider + ?Sized,
+    {
+        localized::resolve_localized_child(
+            self,
+            display_name.as_ref(),
+            &Localizer::new(env),
+        )
+    }
+
+    /// Returns true if this path and `other` are on the same device.
+    ///
+    /// Files can only be [renamed] atomically within the same device. To
+    /// check whether a file can be renamed to a path that does not exist yet,
+    /// the parent directory of that path can be given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file does not exist or cannot be accessed,
+    /// or if device IDs are not supported on this platform.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs;
+    /// use std::io;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let path = BasePath::new(Path::new("/foo/bar"))?;
+    /// let target = BasePath::new(Path::new("/baz/bar"))?;
+    /// if path.same_device(target.parent().unwrap().unwrap())? {
+    ///     fs::rename(&*path, &*target)?;
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [renamed]: ::std::fs::rename
+    #[inline]
+    pub fn same_device<P>(&self, other: P) -> io::Result<bool>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(imp::device_id(self.as_path())? == imp::device_id(other.as_ref())?)
+    }
+
+    /// Returns true if this path and `other` refer to the same file.
+    ///
+    /// This method compares the [identifiers] of the files, so it is more
+    /// reliable than comparing [normalized] paths. For example, it can detect
+    /// hard links and bind mounts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`file_id`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use normpath::PathExt;
+    ///
+    /// let path = std::env::current_dir()?.normalize()?;
+    /// assert!(path.same_file(path.join("."))?);
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [`file_id`]: Self::file_id
+    /// [identifiers]: Self::file_id
+    /// [normalized]: Self::normalize
+    #[inline]
+    pub fn same_file<P>(&self, other: P) -> io::Result<bool>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(imp::file_id(self.as_path())? == imp::file_id(other.as_ref())?)
+    }
+
+    /// Equivalent to [`PathExt::shorten`].
+    #[inline]
+    pub fn shorten(&self) -> io::Result<Cow<'_, Self>> {
+        self.as_path().shorten().map(cow_path_into_base_path)
+    }
+
+    /// Removes the [verbatim] prefix from this path if the result would refer
+    /// to the same file.
+    ///
+    /// This method is the inverse of [`to_verbatim`]. Only paths using the
+    /// `\\?\` prefix for a drive or UNC share can be simplified. Since
+    /// verbatim paths are not normalized by Windows, their prefix is only
+    /// removed if the result is shorter than `MAX_PATH` and has no components
+    /// that would be interpreted differently, such as `..`, reserved names
+    /// like "NUL", and names ending with a period or space. Otherwise, this
+    /// path is returned unchanged.
+    ///
+    /// This path is interpreted using Windows syntax on every platform, and
+    /// the file system is never accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// #[track_caller]
+    /// fn test(result: &str, path: &str) {
+    ///     let path = BasePath::try_new(path).unwrap();
+    ///     assert_eq!(Path::new(result), &*path.simplified());
+    /// }
+    ///
+    /// test(r"C:\foo\bar", r"\\?\C:\foo\bar");
+    /// test(r"\\server\share\foo", r"\\?\UNC\server\share\foo");
+    /// test(r"\\?\C:\foo\nul", r"\\?\C:\foo\nul");
+    /// test(r"\\?\C:\foo.\bar", r"\\?\C:\foo.\bar");
+    /// ```
+    ///
+    /// [`to_verbatim`]: Self::to_verbatim
+    /// [verbatim]: ::std::path::Prefix::is_verbatim
+    #[inline]
+    #[must_use]
+    pub fn simplified(&self) -> Cow<'_, Self> {
+        verbatim::simplify(&self.0)
+            .map_or(Cow::Borrowed(self), cow_os_str_into_base_path)
+    }
+
+    /// Splits this path into its prefix and the relative path that follows
+    /// it.
+    ///
+    /// On Windows, the prefix is parsed the same way as [`Prefix`], and the
+    /// root separator that follows it is not included in either part. Use
+    /// [`has_root`] to check whether a [`PrefixInfo::Disk`] prefix is
+    /// followed by a root. On other platforms, the prefix is
+    /// [`PrefixInfo::PosixRoot`] if this path is absolute.
+    ///
+    /// Returns [`None`] if this path has no prefix, which is only possible
+    /// for relative paths on platforms other than Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    /// use normpath::PrefixInfo;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = BasePath::try_new(r"\\server\share\foo\bar").unwrap();
+    ///     assert_eq!(
+    ///         Some((
+    ///             PrefixInfo::Unc(OsStr::new("server"), OsStr::new("share")),
+    ///             Path::new(r"foo\bar"),
+    ///         )),
+    ///         path.split_prefix(),
+    ///     );
+    /// } else {
+    ///     let path = BasePath::try_new("/foo/bar").unwrap();
+    ///     assert_eq!(
+    ///         Some((PrefixInfo::PosixRoot, Path::new("foo/bar"))),
+    ///         path.split_prefix(),
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// [`has_root`]: Self::has_root
+    /// [`Prefix`]: ::std::path::Prefix
+    #[inline]
+    #[must_use]
+    pub fn split_prefix(&self) -> Option<(PrefixInfo<'_>, &Path)> {
+        prefix::split_prefix(self)
+    }
+
+    /// Equivalent to [`Path::starts_with`].
+    #[inline]
+    #[must_use]
+    pub fn starts_with<P>(&self, base: P) -> bool
+    where
+        P: AsRef<Path>,
+    {
+        self.as_path().starts_with(base)
+    }
+
+    /// Returns the part of this path that follows `base`.
+    ///
+    /// Paths are compared by components, like [`Path::strip_prefix`]. The
+    /// result can be joined to `base` using [`join_rel`] to recreate an
+    /// equivalent path.
+    ///
+    /// Returns [`None`] if this path does not start with `base` or if the
+    /// remaining path contains a `..` component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let (base, path) = if cfg!(windows) {
+    ///     (r"X:\foo", r"X:\foo\bar\baz")
+    /// } else {
+    ///     ("/foo", "/foo/bar/baz")
+    /// };
+    /// let base = BasePath::try_new(base).unwrap();
+    /// let path = BasePath::try_new(path).unwrap();
+    ///
+    /// let rel_path = path.strip_base(base).unwrap();
+    /// assert_eq!(Path::new("bar").join("baz"), rel_path.as_path());
+    /// assert_eq!(path, base.join_rel(rel_path));
+    ///
+    /// assert_eq!(None, base.strip_base(path));
+    /// ```
+    ///
+    /// [`join_rel`]: Self::join_rel
+    #[inline]
+    #[must_use]
+    pub fn strip_base(&self, base: &Self) -> Option<&RelPath> {
+        self.as_path()
+            .strip_prefix(base)
+            .ok()
+            .and_then(|x| RelPath::try_new(x).ok())
+    }
+
+    /// Equivalent to [`Path::symlink_metadata`].
+    #[inline]
+    pub fn symlink_metadata(&self) -> io::Result<Metadata> {
+        self.as_path().symlink_metadata()
+    }
+
+    /// Equivalent to [`to_posix_with`] using the default configuration for
+    /// [`PosixStyle::Cygwin`].
+    ///
+    /// [`to_posix_with`]: Self::to_posix_with
+    #[inline]
+    #[must_use]
+    pub fn to_cygwin(&self) -> Option<PathBuf> {
+        self.to_posix_with(&PosixTranslator::new(PosixStyle::Cygwin))
+    }
+
+    /// Converts this path to a `file:` URL, as described by [RFC 8089].
+    ///
+    /// Characters that are not allowed in URL paths are percent-encoded. On
+    /// Unix, bytes that are not valid UTF-8 are also percent-encoded, so the
+    /// result can always be converted back using
+    /// [`BasePathBuf::from_file_url`].
+    ///
+    /// On Windows, drive prefixes become the first segment of the path, and
+    /// UNC prefixes become the host and first segment. [Verbatim] prefixes are
+    /// converted to the equivalent prefix that is not verbatim.
+    ///
+    /// Components are not normalized, so this path should usually be
+    /// [normalized] first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this path is relative or has a prefix that cannot
+    /// be represented as a URL, such as a [device namespace] prefix. On
+    /// Windows, an error will also be returned for paths that are not valid
+    /// Unicode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use normpath::error::FileUrlError;
+    /// use normpath::BasePath;
+    ///
+    /// if cfg!(windows) {
+    ///     assert_eq!(
+    ///         "file://server/share/foo%20bar",
+    ///         BasePath::try_new(r"\\server\share\foo bar")
+    ///             .unwrap()
+    ///             .to_file_url()?,
+    ///     );
+    /// } else {
+    ///     assert_eq!(
+    ///         "file:///foo/bar%23baz",
+    ///         BasePath::try_new("/foo/bar#baz").unwrap().to_file_url()?,
+    ///     );
+    /// }
+    /// #
+    /// # Ok::<_, FileUrlError>(())
+    /// ```
+    ///
+    /// [device namespace]: ::std::path::Prefix::DeviceNS
+    /// [normalized]: Self::normalize
+    /// [RFC 8089]: https://datatracker.ietf.org/doc/html/rfc8089
+    /// [verbatim]: ::std::path::Prefix::is_verbatim
+    #[inline]
+    pub fn to_file_url(&self) -> Result<String, FileUrlError> {
+        url::to_file_url(self)
+    }
+
+    /// Equivalent to [`to_posix_with`] using the default configuration for
+    /// [`PosixStyle::Msys`].
+    ///
+    /// [`to_posix_with`]: Self::to_posix_with
+    #[inline]
+    #[must_use]
+    pub fn to_msys(&self) -> Option<PathBuf> {
+        self.to_posix_with(&PosixTranslator::new(PosixStyle::Msys))
+    }
+
+    /// Translates this Windows path to a POSIX path for the environment
+    /// configured by `translator`.
+    ///
+    /// This path is interpreted using Windows syntax on every platform, and
+    /// the file system is never accessed. For more information, see
+    /// [`PosixTranslator`].
+    ///
+    /// Returns [`None`] if this path is not absolute or cannot be represented
+    /// in the environment. For example, a [verbatim] path cannot be
+    /// translated if it contains `..` components, and UNC paths can only be
+    /// translated for WSL if they use the `\\wsl$` server.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// let path = BasePath::try_new(r"C:\Users\foo\..\bar").unwrap();
+    /// assert_eq!(Some(Path::new("/mnt/c/Users/bar")), path.to_wsl().as_deref());
+    /// assert_eq!(Some(Path::new("/c/Users/bar")), path.to_msys().as_deref());
+    /// assert_eq!(
+    ///     Some(Path::new("/cygdrive/c/Users/bar")),
+    ///     path.to_cygwin().as_deref(),
+    /// );
+    ///
+    /// let path = BasePath::try_new(r"\\wsl$\Ubuntu\home").unwrap();
+    /// assert_eq!(Some(Path::new("/home")), path.to_wsl().as_deref());
+    /// assert_eq!(
+    ///     Some(Path::new("//wsl$/Ubuntu/home")),
+    ///     path.to_msys().as_deref(),
+    /// );
+    /// ```
+    ///
+    /// [verbatim]: ::std::path::Prefix::is_verbatim
+    #[inline]
+    #[must_use]
+    pub fn to_posix_with(
+        &self,
+        translator: &PosixTranslator,
+    ) -> Option<PathBuf> {
+        translate::to_posix(&self.0, translator).map(Into::into)
+    }
+
+    /// Converts this path to a [verbatim] path, which allows it to exceed the
+    /// `MAX_PATH` limit on Windows.
+    ///
+    /// Before adding the `\\?\` or `\\?\UNC\` prefix, this path is
+    /// normalized lexically in the same way that Windows would normalize it.
+    /// Separators are made consistent, `.` and `..` components are removed,
+    /// and trailing periods and spaces are removed. Paths that are already
+    /// verbatim are returned unchanged.
+    ///
+    /// This path is interpreted using Windows syntax on every platform, and
+    /// the file system is never accessed.
+    ///
+    /// Returns [`None`] if this path does not start with a drive, UNC, or
+    /// device prefix followed by a root, or if it ends with a reserved name
+    /// like "NUL". Windows would resolve those paths differently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// #[track_caller]
+    /// fn test(result: Option<&str>, path: &str) {
+    ///     let path = BasePath::try_new(path).unwrap();
+    ///     assert_eq!(
+    ///         result.map(Path::new),
+    ///         path.to_verbatim().as_ref().map(|x| x.as_path()),
+    ///     );
+    /// }
+    ///
+    /// test(Some(r"\\?\C:\foo\baz"), r"C:\foo\bar\..\baz.");
+    /// test(Some(r"\\?\UNC\server\share\foo"), r"\\server\share\foo");
+    /// test(Some(r"\\?\X:\foo\..\bar"), r"\\?\X:\foo\..\bar");
+    /// test(None, r"C:foo");
+    /// test(None, r"C:\foo\nul.txt");
+    /// ```
+    ///
+    /// [verbatim]: ::std::path::Prefix::is_verbatim
+    #[inline]
+    #[must_use]
+    pub fn to_verbatim(&self) -> Option<Cow<'_, Self>> {
+        verbatim::to_verbatim(&self.0).map(cow_os_str_into_base_path)
+    }
+
+    /// Equivalent to [`to_posix_with`] using the default configuration for
+    /// [`PosixStyle::Wsl`].
+    ///
+    /// [`to_posix_with`]: Self::to_posix_with
+    #[inline]
+    #[must_use]
+    pub fn to_wsl(&self) -> Option<PathBuf> {
+        self.to_posix_with(&PosixTranslator::new(PosixStyle::Wsl))
+    }
+
+    /// Equivalent to [`Path::try_exists`].
+    #[inline]
+    pub fn try_exists(&self) -> io::Result<bool> {
+        self.as_path().try_exists()
+    }
+
+    /// Equivalent to [`PathExt::try_localize_name`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    pub fn try_localize_name(&self) -> Result<Localized<'_>, LocalizeError> {
+        self.as_path().try_localize_name()
+    }
+
+    /// Equivalent to [`PathExt::try_localize_name_with`].
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[inline]
+    pub fn try_localize_name_with<E>(
+        &self,
+        env: &E,
+    ) -> Result<Localized<'_>, LocalizeError>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        self.as_path().try_localize_name_with(env)
+    }
+
+    /// Checks whether each component of this path can be used as a file name
+    /// on `platform`.
+    ///
+    /// This method is useful for finding files that cannot be copied to
+    /// another platform, such as a file named "CON" that was created on
+    /// Linux. Only [normal] components are checked. The file system is never
+    /// accessed, so the validation can be performed for any platform on every
+    /// platform.
+    ///
+    /// Components are invalid for these reasons:
+    /// - [`ReservedName`] - The name refers to a device on Windows, even if it
+    ///   has an extension.
+    /// - [`ForbiddenCharacter`] - The name contains one of `"*/:<>?\|` on
+    ///   Windows.
+    /// - [`ControlCharacter`] - The name contains a character in the range
+    ///   U+0001 to U+001F on Windows, or it contains U+0000.
+    /// - [`TrailingDotOrSpace`] - The name ends with a period or space on
+    ///   Windows.
+    /// - [`InvalidEncoding`] - The name is not valid UTF-8 on MacOS.
+    /// - [`TooLong`] - The name is longer than 255 UTF-16 code units on
+    ///   Windows or 255 bytes on other platforms.
+    ///
+    /// [`Platform::Portable`] applies all of these rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing every invalid component and each reason that
+    /// it is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use normpath::error::InvalidComponentKind;
+    /// use normpath::BasePath;
+    /// use normpath::Platform;
+    ///
+    /// let path = if cfg!(windows) {
+    ///     r"X:\foo\nul.txt\bar."
+    /// } else {
+    ///     "/foo/nul.txt/bar."
+    /// };
+    /// let path = BasePath::try_new(path).unwrap();
+    /// assert_eq!(Ok(()), path.validate_for(Platform::Linux));
+    ///
+    /// let error = path.validate_for(Platform::Windows).unwrap_err();
+    /// let components: Vec<_> = error
+    ///     .components()
+    ///     .iter()
+    ///     .map(|x| (x.name(), x.kind()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     [
+    ///         ("nul.txt".as_ref(), InvalidComponentKind::ReservedName),
+    ///         ("bar.".as_ref(), InvalidComponentKind::TrailingDotOrSpace),
+    ///     ],
+    ///     &*components,
+    /// );
+    /// ```
+    ///
+    /// [`ControlCharacter`]: super::error::InvalidComponentKind::ControlCharacter
+    /// [`ForbiddenCharacter`]: super::error::InvalidComponentKind::ForbiddenCharacter
+    /// [`InvalidEncoding`]: super::error::InvalidComponentKind::InvalidEncoding
+    /// [normal]: Component::Normal
+    /// [`ReservedName`]: super::error::InvalidComponentKind::ReservedName
+    /// [`TooLong`]: super::error::InvalidComponentKind::TooLong
+    /// [`TrailingDotOrSpace`]: super::error::InvalidComponentKind::TrailingDotOrSpace
+    #[inline]
+    pub fn validate_for(
+        &self,
+        platform: Platform,
+    ) -> Result<(), ValidationError> {
+        validate::validate(self, platform)
+    }
+
+    /// Equivalent to [`Path::with_extension`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePath;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = BasePath::try_new(r"X:\foo\bar.rs").unwrap();
+    ///     assert_eq!(Path::new(r"X:\foo\bar.txt"), path.with_extension("txt"));
+    ///
+    ///     let path = BasePath::try_new(r"X:\").unwrap();
+    ///     assert_eq!(Path::new(r"X:\"), path.with_extension("txt"));
+    /// }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_extension<S>(&self, extension: S) -> BasePathBuf
+    where
+        S: AsRef<OsStr>,
+    {
+        let mut base = self.to_owned();
+        let _ = base.set_extension(extension);
+        base
+    }
+
+    /// Equivalent to [`Path::with_file_name`], but only accepts a single file
+    /// name.
+    ///
+    /// For more information, see [`BasePathBuf::set_file_name`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `file_name` is not a single file name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// # use normpath::error::InvalidFileNameError;
+    /// use normpath::BasePath;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = BasePath::try_new(r"X:\foo\bar.rs").unwrap();
+    ///     assert_eq!(
+    ///         Path::new(r"X:\foo\baz.txt"),
+    ///         path.with_file_name("baz.txt")?,
+    ///     );
+    ///
+    ///     assert!(path.with_file_name(r"Y:\baz.txt").is_err());
+    /// }
+    /// #
+    /// # Ok::<_, InvalidFileNameError>(())
+    /// ```
+    #[inline]
+    pub fn with_file_name<S>(
+        &self,
+        file_name: S,
+    ) -> Result<BasePathBuf, InvalidFileNameError>
+    where
+        S: AsRef<OsStr>,
+    {
+        let mut base = self.to_owned();
+        base.set_file_name(file_name).map(|()| base)
+    }
+}
+
+impl AsRef<OsStr> for BasePath {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for BasePath {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl AsRef<Self> for BasePath {
+    #[inline]
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl Clone for Box<BasePath> {
+    #[inline]
+    fn clone(&self) -> Self {
+        (**self).to_owned().into_boxed_base_path()
+    }
+}
+
+impl From<&BasePath> for Arc<BasePath> {
+    #[inline]
+    fn from(value: &BasePath) -> Self {
+        let path = Arc::into_raw(Arc::<OsStr>::from(&value.0));
+        // SAFETY: This struct has the same layout as [OsStr].
+        unsafe { Arc::from_raw(path as *const BasePath) }
+    }
+}
+
+impl From<&BasePath> for Box<BasePath> {
+    #[inline]
+    fn from(value: &BasePath) -> Self {
+        value.to_owned().into_boxed_base_path()
+    }
+}
+
+impl From<Cow<'_, BasePath>> for Box<BasePath> {
+    #[inline]
+    fn from(value: Cow<'_, BasePath>) -> Self {
+        value.into_owned().into_boxed_base_path()
+    }
+}
+
+impl<'a> From<&'a BasePath> for Cow<'a, BasePath> {
+    #[inline]
+    fn from(value: &'a BasePath) -> Self {
+        Cow::Borrowed(value)
+    }
+}
+
+impl From<&BasePath> for Rc<BasePath> {
+    #[inline]
+    fn from(value: &BasePath) -> Self {
+        let path = Rc::into_raw(Rc::<OsStr>::from(&value.0));
+        // SAFETY: This struct has the same layout as [OsStr].
+        unsafe { Rc::from_raw(path as *const BasePath) }
+    }
+}
+
+impl PartialEq<Path> for BasePath {
+    #[inline]
+    fn eq(&self, other: &Path) -> bool {
+        &self.0 == other.as_os_str()
+    }
+}
+
+impl PartialEq<BasePath> for Path {
+    #[inline]
+    fn eq(&self, other: &BasePath) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<Path> for BasePath {
+    #[inline]
+    fn partial_cmp(&self, other: &Path) -> Option<Ordering> {
+        self.0.partial_cmp(other.as_os_str())
+    }
+}
+
+impl PartialOrd<BasePath> for Path {
+    #[inline]
+    fn partial_cmp(&self, other: &BasePath) -> Option<Ordering> {
+        other.partial_cmp(self)
+    }
+}
+
+impl ToOwned for BasePath {
+    type Owned = BasePathBuf;
+
+    #[inline]
+    fn to_owned(&self) -> Self::Owned {
+        BasePathBuf(self.0.to_owned().into())
+    }
+}
+
+/// An owned path that has a [prefix] on Windows.
+///
+/// For more information, see [`BasePath`].
+///
+/// [prefix]: ::std::path::Prefix
+#[derive(Clone, Debug)]
+pub struct BasePathBuf(pub(super) PathBuf);
+
+impl BasePathBuf {
+    /// Equivalent to [`BasePath::new`] but returns an owned path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = r"X:\foo\bar";
+    ///     assert_eq!(Path::new(path), BasePathBuf::new(path)?);
+    ///
+    ///     assert!(BasePathBuf::new(r"foo\bar").is_ok());
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    #[inline]
+    pub fn new<P>(path: P) -> io::Result<Self>
+    where
+        P: Into<PathBuf>,
+    {
+        Self::try_new(path).or_else(|x| imp::to_base(&x.0))
+    }
+
+    /// Equivalent to [`BasePath::try_new`] but returns an owned path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// # use normpath::error::MissingPrefixBufError;
+    /// use normpath::BasePathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = r"X:\foo\bar";
+    ///     assert_eq!(Path::new(path), BasePathBuf::try_new(path)?);
+    ///
+    ///     assert!(BasePathBuf::try_new(r"foo\bar").is_err());
+    /// }
+    /// #
+    /// # Ok::<_, MissingPrefixBufError>(())
+    /// ```
+    #[inline]
+    pub fn try_new<P>(path: P) -> Result<Self, MissingPrefixBufError>
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        if imp::is_base(&path) {
+            Ok(Self(path))
+        } else {
+            Err(MissingPrefixBufError(path))
+        }
+    }
+
+    /// Creates a new base path by expanding `~` and environment variables in
+    /// `path`, as a shell would.
+    ///
+    /// A leading `~` is replaced with [`EnvProvider::home_dir`], and a leading
+    /// `~user` is replaced with [`EnvProvider::user_home_dir`]. Variables are
+    /// then expanded using the [default syntax] for the platform. To choose
+    /// the syntax, use [`expand_shell_as`].
+    ///
+    /// The result is passed to [`new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is not defined, a home directory is
+    /// unknown, a variable reference is malformed, or [`new`] fails. The error
+    /// will wrap an [`ExpandError`] in the first three cases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::ffi::OsStr;
+    /// use std::ffi::OsString;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    /// use normpath::EnvProvider;
+    /// use normpath::ShellSyntax;
+    ///
+    /// struct FakeEnv;
+    ///
+    /// impl EnvProvider for FakeEnv {
+    ///     fn var(&self, name: &OsStr) -> Option<OsString> {
+    ///         (name == "HOME").then(|| "/home/foo".into())
+    ///     }
+    /// }
+    ///
+    /// if cfg!(unix) {
+    ///     assert_eq!(
+    ///         Path::new("/home/foo/bar/baz"),
+    ///         BasePathBuf::expand_shell("~/bar/baz", &FakeEnv)?,
+    ///     );
+    ///     assert_eq!(
+    ///         Path::new("/home/foo/.cache"),
+    ///         BasePathBuf::expand_shell("${HOME}/.cache", &FakeEnv)?,
+    ///     );
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [default syntax]: ShellSyntax::default
+    /// [`expand_shell_as`]: Self::expand_shell_as
+    /// [`ExpandError`]: super::error::ExpandError
+    /// [`new`]: Self::new
+    #[inline]
+    pub fn expand_shell<P, E>(path: P, env: &E) -> io::Result<Self>
+    where
+        P: AsRef<OsStr>,
+        E: EnvProvider + ?Sized,
+    {
+        Self::expand_shell_as(path, ShellSyntax::default(), env)
+    }
+
+    /// Equivalent to [`expand_shell`] but uses the given syntax for
+    /// variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::ffi::OsStr;
+    /// use std::ffi::OsString;
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    /// use normpath::EnvProvider;
+    /// use normpath::ShellSyntax;
+    ///
+    /// struct FakeEnv;
+    ///
+    /// impl EnvProvider for FakeEnv {
+    ///     fn var(&self, name: &OsStr) -> Option<OsString> {
+    ///         (name == "APPDATA").then(|| r"X:\Users\foo\AppData".into())
+    ///     }
+    /// }
+    ///
+    /// if cfg!(windows) {
+    ///     assert_eq!(
+    ///         Path::new(r"X:\Users\foo\AppData\bar"),
+    ///         BasePathBuf::expand_shell_as(
+    ///             r"%APPDATA%\bar",
+    ///             ShellSyntax::Windows,
+    ///             &FakeEnv,
+    ///         )?,
+    ///     );
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [`expand_shell`]: Self::expand_shell
+    pub fn expand_shell_as<P, E>(
+        path: P,
+        syntax: ShellSyntax,
+        env: &E,
+    ) -> io::Result<Self>
+    where
+        P: AsRef<OsStr>,
+        E: EnvProvider + ?Sized,
+    {
+        let path =
+            expand::expand(path.as_ref(), syntax, env).map_err(|x| {
+                let kind = match x {
+                    ExpandError::InvalidSyntax => io::ErrorKind::InvalidInput,
+                    _ => io::ErrorKind::NotFound,
+                };
+                io::Error::new(kind, x)
+            })?;
+        Self::new(path)
+    }
+
+    /// Converts a `file:` URL to a path, as described by [RFC 8089].
+    ///
+    /// This method reverses [`BasePath::to_file_url`]. A host of `localhost`
+    /// is treated as if no host was given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not a valid `file:` URL or cannot be
+    /// represented as a path on this platform. For example, on Windows, the
+    /// URL must have a host or a drive letter. On other platforms, it cannot
+    /// have a host.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// # use normpath::error::FileUrlError;
+    /// use normpath::BasePathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     assert_eq!(
+    ///         Path::new(r"C:\foo bar"),
+    ///         BasePathBuf::from_file_url("file:///C:/foo%20bar")?,
+    ///     );
+    ///     assert_eq!(
+    ///         Path::new(r"\\server\share\foo"),
+    ///         BasePathBuf::from_file_url("file://server/share/foo")?,
+    ///     );
+    /// } else {
+    ///     assert_eq!(
+    ///         Path::new("/foo bar"),
+    ///         BasePathBuf::from_file_url("file:///foo%20bar")?,
+    ///     );
+    /// }
+    /// #
+    /// # Ok::<_, FileUrlError>(())
+    /// ```
+    ///
+    /// [RFC 8089]: https://datatracker.ietf.org/doc/html/rfc8089
     #[inline]
-    fn as_ref(&self) -> &Path {
-        self.as_path()
+    pub fn from_file_url(url: &str) -> Result<Self, FileUrlError> {
+        url::from_file_url(url)
     }
-}
 
-impl AsRef<Self> for BasePath {
+    /// Equivalent to [`from_posix_with`] using the default configuration for
+    /// [`PosixStyle::Cygwin`].
+    ///
+    /// [`from_posix_with`]: Self::from_posix_with
     #[inline]
-    fn as_ref(&self) -> &Self {
-        self
+    #[must_use]
+    pub fn from_cygwin<P>(path: P) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_posix_with(path, &PosixTranslator::new(PosixStyle::Cygwin))
     }
-}
 
-impl<'a> From<&'a BasePath> for Cow<'a, BasePath> {
+    /// Equivalent to [`from_posix_with`] using the default configuration for
+    /// [`PosixStyle::Msys`].
+    ///
+    /// [`from_posix_with`]: Self::from_posix_with
     #[inline]
-    fn from(value: &'a BasePath) -> Self {
-        Cow::Borrowed(value)
+    #[must_use]
+    pub fn from_msys<P>(path: P) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_posix_with(path, &PosixTranslator::new(PosixStyle::Msys))
     }
-}
 
-impl PartialEq<Path> for BasePath {
+    /// Translates a POSIX path from the environment configured by
+    /// `translator` to a Windows path.
+    ///
+    /// This method reverses [`BasePath::to_posix_with`]. The result uses
+    /// Windows syntax on every platform.
+    ///
+    /// Returns [`None`] if `path` is not absolute or cannot be represented on
+    /// Windows. For example, paths outside of the mount root can only be
+    /// translated for WSL if a [distribution] is configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    /// use normpath::PosixStyle;
+    /// use normpath::PosixTranslator;
+    ///
+    /// #[track_caller]
+    /// fn test(result: &str, path: Option<BasePathBuf>) {
+    ///     assert_eq!(Some(Path::new(result)), path.as_ref().map(|x| x.as_path()));
+    /// }
+    ///
+    /// test(r"C:\Users\foo", BasePathBuf::from_wsl("/mnt/c/Users/foo"));
+    /// test(r"C:\Users\foo", BasePathBuf::from_msys("/c/Users/foo"));
+    /// test(r"\\server\share\foo", BasePathBuf::from_msys("//server/share/foo"));
+    /// test(
+    ///     r"C:\Users\foo",
+    ///     BasePathBuf::from_cygwin("/cygdrive/c/Users/foo"),
+    /// );
+    ///
+    /// assert_eq!(None, BasePathBuf::from_wsl("/home/foo"));
+    /// test(
+    ///     r"\\wsl$\Ubuntu\home\foo",
+    ///     BasePathBuf::from_posix_with(
+    ///         "/home/foo",
+    ///         &PosixTranslator::new(PosixStyle::Wsl).wsl_distro("Ubuntu"),
+    ///     ),
+    /// );
+    /// ```
+    ///
+    /// [distribution]: PosixTranslator::wsl_distro
     #[inline]
-    fn eq(&self, other: &Path) -> bool {
-        &self.0 == other.as_os_str()
+    #[must_use]
+    pub fn from_posix_with<P>(
+        path: P,
+        translator: &PosixTranslator,
+    ) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        translate::from_posix(path.as_ref().as_os_str(), translator)
+            .and_then(|x| Self::try_new(x).ok())
     }
-}
 
-impl PartialEq<BasePath> for Path {
+    /// Equivalent to [`from_posix_with`] using the default configuration for
+    /// [`PosixStyle::Wsl`].
+    ///
+    /// [`from_posix_with`]: Self::from_posix_with
     #[inline]
-    fn eq(&self, other: &BasePath) -> bool {
-        other == self
+    #[must_use]
+    pub fn from_wsl<P>(path: P) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_posix_with(path, &PosixTranslator::new(PosixStyle::Wsl))
     }
-}
 
-impl PartialOrd<Path> for BasePath {
+    /// Returns the current working directory as the user navigated to it.
+    ///
+    /// Shells set the `PWD` environment variable to the logical working
+    /// directory, which can contain symbolic links. It is used if it is
+    /// absolute, has no `..` components, and refers to the same file as
+    /// [`env::current_dir`]. Otherwise, the physical working directory is
+    /// returned. Therefore, this method is most useful on Unix, where
+    /// [`env::current_dir`] resolves symbolic links.
+    ///
+    /// Use [`LogicalPathBuf`] to continue navigating from this path
+    /// lexically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the physical working directory cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::env;
+    ///
+    /// use normpath::BasePathBuf;
+    ///
+    /// let path = BasePathBuf::logical_current_dir()?;
+    /// assert!(path.same_file(env::current_dir()?)?);
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [`env::current_dir`]: ::std::env::current_dir
+    /// [`LogicalPathBuf`]: super::LogicalPathBuf
     #[inline]
-    fn partial_cmp(&self, other: &Path) -> Option<Ordering> {
-        self.0.partial_cmp(other.as_os_str())
+    pub fn logical_current_dir() -> io::Result<Self> {
+        Self::logical_current_dir_with(&SystemEnv)
     }
-}
 
-impl PartialOrd<BasePath> for Path {
+    /// Equivalent to [`logical_current_dir`] but reads environment variables
+    /// from `env`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the physical working directory cannot be read.
+    ///
+    /// [`logical_current_dir`]: Self::logical_current_dir
     #[inline]
-    fn partial_cmp(&self, other: &BasePath) -> Option<Ordering> {
-        other.partial_cmp(self)
+    pub fn logical_current_dir_with<E>(env: &E) -> io::Result<Self>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        logical::current_dir(env)
     }
-}
-
-impl ToOwned for BasePath {
-    type Owned = BasePathBuf;
 
+    /// Equivalent to [`PathBuf::with_capacity`], but the path is initialized
+    /// with `base`, since base paths cannot be empty on Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use normpath::BasePath;
+    /// use normpath::BasePathBuf;
+    ///
+    /// let base = if cfg!(windows) { r"X:\" } else { "/" };
+    /// let base = BasePath::try_new(base).unwrap();
+    ///
+    /// let path = BasePathBuf::with_capacity(base, 64);
+    /// assert_eq!(base, path);
+    /// assert!(path.capacity() >= 64);
+    /// ```
     #[inline]
-    fn to_owned(&self) -> Self::Owned {
-        BasePathBuf(self.0.to_owned().into())
+    #[must_use]
+    pub fn with_capacity(base: &BasePath, capacity: usize) -> Self {
+        let mut path = PathBuf::with_capacity(capacity.max(base.0.len()));
+        path.as_mut_os_string().push(&base.0);
+        Self(path)
     }
-}
-
-/// An owned path that has a [prefix] on Windows.
-///
-/// For more information, see [`BasePath`].
-///
-/// [prefix]: ::std::path::Prefix
-#[derive(Clone, Debug)]
-pub struct BasePathBuf(pub(super) PathBuf);
 
-impl BasePathBuf {
-    /// Equivalent to [`BasePath::new`] but returns an owned path.
+    /// Appends `extension` to the extension of the file name, separated by a
+    /// period.
+    ///
+    /// This method is similar to [`set_extension`], but it keeps any
+    /// existing extension. Returns [`false`] and does nothing if
+    /// [`BasePath::file_name`] returns [`None`]. If `extension` is empty, the
+    /// path is not modified.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use std::io;
     /// use std::path::Path;
     ///
     /// use normpath::BasePathBuf;
     ///
     /// if cfg!(windows) {
-    ///     let path = r"X:\foo\bar";
-    ///     assert_eq!(Path::new(path), BasePathBuf::new(path)?);
+    ///     let mut path = BasePathBuf::try_new(r"X:\foo.tar").unwrap();
+    ///     assert!(path.add_extension("gz"));
+    ///     assert_eq!(Path::new(r"X:\foo.tar.gz"), path);
     ///
-    ///     assert!(BasePathBuf::new(r"foo\bar").is_ok());
+    ///     let mut path = BasePathBuf::try_new(r"X:\").unwrap();
+    ///     assert!(!path.add_extension("gz"));
     /// }
-    /// #
-    /// # Ok::<_, io::Error>(())
     /// ```
+    ///
+    /// [`set_extension`]: Self::set_extension
     #[inline]
-    pub fn new<P>(path: P) -> io::Result<Self>
+    pub fn add_extension<S>(&mut self, extension: S) -> bool
     where
-        P: Into<PathBuf>,
+        S: AsRef<OsStr>,
     {
-        Self::try_new(path).or_else(|x| imp::to_base(&x.0))
+        let Some(file_name) = self.file_name() else {
+            return false;
+        };
+
+        let extension = extension.as_ref();
+        if !extension.is_empty() {
+            let mut file_name = file_name.to_owned();
+            file_name.push(".");
+            file_name.push(extension);
+            self.0.set_file_name(file_name);
+        }
+        true
     }
 
-    /// Equivalent to [`BasePath::try_new`] but returns an owned path.
+    /// Equivalent to [`PathBuf::capacity`].
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Removes all components except for the [prefix] and root directory.
+    ///
+    /// Unlike [`PathBuf::clear`], this method does not make the path empty,
+    /// since base paths must keep their prefix on Windows. On other
+    /// platforms, the path becomes `/` if it has a root, or it becomes empty
+    /// otherwise. The capacity of the path is not changed.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::path::Path;
     ///
-    /// # use normpath::error::MissingPrefixBufError;
     /// use normpath::BasePathBuf;
     ///
     /// if cfg!(windows) {
-    ///     let path = r"X:\foo\bar";
-    ///     assert_eq!(Path::new(path), BasePathBuf::try_new(path)?);
+    ///     let mut path = BasePathBuf::try_new(r"X:\foo\bar").unwrap();
+    ///     path.clear();
+    ///     assert_eq!(Path::new(r"X:\"), path);
     ///
-    ///     assert!(BasePathBuf::try_new(r"foo\bar").is_err());
+    ///     let mut path = BasePathBuf::try_new(r"\\server\share\foo").unwrap();
+    ///     path.clear();
+    ///     assert_eq!(Path::new(r"\\server\share\"), path);
     /// }
-    /// #
-    /// # Ok::<_, MissingPrefixBufError>(())
     /// ```
+    ///
+    /// [prefix]: ::std::path::Prefix
     #[inline]
-    pub fn try_new<P>(path: P) -> Result<Self, MissingPrefixBufError>
-    where
-        P: Into<PathBuf>,
-    {
-        let path = path.into();
-        if imp::is_base(&path) {
-            Ok(Self(path))
-        } else {
-            Err(MissingPrefixBufError(path))
+    pub fn clear(&mut self) {
+        let mut len = 0;
+        for component in self.components() {
+            match component {
+                Component::Prefix(prefix) => len += prefix.as_os_str().len(),
+                // Roots are always a single separator, but they are sometimes
+                // implied by a prefix without a separator.
+                Component::RootDir => len += 1,
+                _ => break,
+            }
         }
+
+        let mut path =
+            mem::take(&mut self.0).into_os_string().into_encoded_bytes();
+        path.truncate(len);
+        // SAFETY: The path is only truncated after a prefix or separator.
+        self.0 =
+            unsafe { OsString::from_encoded_bytes_unchecked(path) }.into();
+    }
+
+    /// Converts this path into a boxed [`BasePath`].
+    #[inline]
+    #[must_use]
+    pub fn into_boxed_base_path(self) -> Box<BasePath> {
+        let path = Box::into_raw(self.0.into_boxed_path());
+        // SAFETY: This struct has the same layout as [Path].
+        unsafe { Box::from_raw(path as *mut BasePath) }
     }
 
     /// Returns the wrapped path as a platform string.
@@ -654,6 +2458,154 @@ impl BasePathBuf {
     {
         imp::push(self, path.as_ref());
     }
+
+    /// Replaces up to `count` of the outermost [extensions] with `extension`.
+    ///
+    /// If the file name has fewer extensions, all of them are replaced. If
+    /// `extension` is empty, the extensions are only removed. Otherwise, it
+    /// can be compound, such as `tar.gz`.
+    ///
+    /// Returns [`false`] and does nothing if [`BasePath::file_name`] returns
+    /// [`None`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let mut path = BasePathBuf::try_new(r"X:\foo.tar.gz").unwrap();
+    ///     assert!(path.replace_extensions(2, "zip"));
+    ///     assert_eq!(Path::new(r"X:\foo.zip"), path);
+    ///
+    ///     let mut path = BasePathBuf::try_new(r"X:\.bashrc").unwrap();
+    ///     assert!(path.replace_extensions(1, "bak"));
+    ///     assert_eq!(Path::new(r"X:\.bashrc.bak"), path);
+    /// }
+    /// ```
+    ///
+    /// [extensions]: BasePath::extensions
+    #[inline]
+    pub fn replace_extensions<S>(&mut self, count: usize, extension: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        let Some(file_name) = self.file_name() else {
+            return false;
+        };
+
+        let file_name = file_name.as_encoded_bytes();
+        let len = extensions::strip_extensions(file_name, count);
+        let mut file_name = lexical::os_str(&file_name[..len]).to_owned();
+        let extension = extension.as_ref();
+        if !extension.is_empty() {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+        self.0.set_file_name(file_name);
+        true
+    }
+
+    /// Equivalent to [`PathBuf::reserve`].
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    /// Equivalent to [`PathBuf::reserve_exact`].
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.0.reserve_exact(additional);
+    }
+
+    /// Equivalent to [`PathBuf::set_extension`].
+    ///
+    /// The extension is only added to the file name, so the [prefix] of the
+    /// path is never modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let mut path = BasePathBuf::try_new(r"X:\foo.rs").unwrap();
+    ///     assert!(path.set_extension("txt"));
+    ///     assert_eq!(Path::new(r"X:\foo.txt"), path);
+    ///     assert!(path.set_extension(""));
+    ///     assert_eq!(Path::new(r"X:\foo"), path);
+    /// }
+    /// ```
+    ///
+    /// [prefix]: ::std::path::Prefix
+    #[inline]
+    pub fn set_extension<S>(&mut self, extension: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        self.0.set_extension(extension)
+    }
+
+    /// Equivalent to [`PathBuf::set_file_name`], but only accepts a single
+    /// file name.
+    ///
+    /// Values with a separator, a [prefix], or a root, as well as `.`, `..`,
+    /// and empty values, are rejected. Otherwise, they could modify more than
+    /// the file name, or even remove the prefix of the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `file_name` is not a single file name. The path is
+    /// not modified in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let mut path = BasePathBuf::try_new(r"X:\foo\bar.rs").unwrap();
+    ///     path.set_file_name("baz.txt").unwrap();
+    ///     assert_eq!(Path::new(r"X:\foo\baz.txt"), path);
+    ///
+    ///     assert!(path.set_file_name(r"Y:\baz.txt").is_err());
+    ///     assert!(path.set_file_name("..").is_err());
+    ///     assert_eq!(Path::new(r"X:\foo\baz.txt"), path);
+    /// }
+    /// ```
+    ///
+    /// [prefix]: ::std::path::Prefix
+    #[inline]
+    pub fn set_file_name<S>(
+        &mut self,
+        file_name: S,
+    ) -> Result<(), InvalidFileNameError>
+    where
+        S: AsRef<OsStr>,
+    {
+        let file_name = file_name.as_ref();
+        check_file_name(file_name)?;
+        self.0.set_file_name(file_name);
+        Ok(())
+    }
+
+    /// Equivalent to [`PathBuf::shrink_to`].
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.0.shrink_to(min_capacity);
+    }
+
+    /// Equivalent to [`PathBuf::shrink_to_fit`].
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
 }
 
 impl AsRef<OsStr> for BasePathBuf {
@@ -695,6 +2647,29 @@ impl Deref for BasePathBuf {
 
 impl Eq for BasePathBuf {}
 
+impl From<Box<BasePath>> for BasePathBuf {
+    #[inline]
+    fn from(value: Box<BasePath>) -> Self {
+        value.into_base_path_buf()
+    }
+}
+
+impl From<BasePathBuf> for Arc<BasePath> {
+    #[inline]
+    fn from(value: BasePathBuf) -> Self {
+        let path = Arc::into_raw(Arc::<OsStr>::from(value.into_os_string()));
+        // SAFETY: This struct has the same layout as [OsStr].
+        unsafe { Arc::from_raw(path as *const BasePath) }
+    }
+}
+
+impl From<BasePathBuf> for Box<BasePath> {
+    #[inline]
+    fn from(value: BasePathBuf) -> Self {
+        value.into_boxed_base_path()
+    }
+}
+
 impl From<BasePathBuf> for Cow<'_, BasePath> {
     #[inline]
     fn from(value: BasePathBuf) -> Self {
@@ -709,6 +2684,15 @@ impl From<BasePathBuf> for OsString {
     }
 }
 
+impl From<BasePathBuf> for Rc<BasePath> {
+    #[inline]
+    fn from(value: BasePathBuf) -> Self {
+        let path = Rc::into_raw(Rc::<OsStr>::from(value.into_os_string()));
+        // SAFETY: This struct has the same layout as [OsStr].
+        unsafe { Rc::from_raw(path as *const BasePath) }
+    }
+}
+
 impl From<BasePathBuf> for PathBuf {
     #[inline]
     fn from(value: BasePathBuf) -> Self {
@@ -18,6 +18,8 @@
 
 use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -75,3 +77,19 @@ r#impl!(BasePath, PathBuf);
 r#impl!(&BasePath, Path);
 r#impl!(&BasePath, Cow<'_, Path>);
 r#impl!(&BasePath, PathBuf);
+
+r#impl!(BasePathBuf, OsStr);
+r#impl!(BasePathBuf, &OsStr);
+r#impl!(BasePathBuf, Cow<'_, OsStr>);
+r#impl!(BasePathBuf, OsString);
+r#impl!(BasePath, OsStr);
+r#impl!(BasePath, &OsStr);
+r#impl!(BasePath, Cow<'_, OsStr>);
+r#impl!(BasePath, OsString);
+r#impl!(&BasePath, OsStr);
+r#impl!(&BasePath, Cow<'_, OsStr>);
+r#impl!(&BasePath, OsString);
+
+r#impl!(BasePathBuf, Box<Path>);
+r#impl!(BasePath, Box<Path>);
+r#impl!(&BasePath, Box<Path>);
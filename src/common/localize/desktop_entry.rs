@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::str;
+
+use super::locale::Locale;
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(ch) => result.push(ch),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+// Parses the entries of the "Desktop Entry" group in a file using the
+// Desktop Entry Specification, such as a ".directory" file written by KDE.
+//
+// https://specifications.freedesktop.org/desktop-entry-spec/latest/
+pub(super) struct DesktopEntry(HashMap<String, String>);
+
+impl DesktopEntry {
+    pub(super) fn parse(contents: &[u8]) -> Option<Self> {
+        let contents = str::from_utf8(contents).ok()?;
+
+        let mut entries = HashMap::new();
+        let mut in_group = false;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(group) =
+                line.strip_prefix('[').and_then(|x| x.strip_suffix(']'))
+            {
+                in_group = group == "Desktop Entry";
+                continue;
+            }
+            if !in_group {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                // Later duplicates are invalid, so the first is kept.
+                let _ = entries
+                    .entry(key.trim_end().to_owned())
+                    .or_insert_with(|| unescape(value.trim_start()));
+            }
+        }
+        Some(Self(entries))
+    }
+
+    // Returns the value of a localized key, using the most specific locale
+    // name that has a value.
+    pub(super) fn localized(
+        &self,
+        key: &str,
+        locale: Option<&Locale>,
+    ) -> Option<&str> {
+        locale
+            .into_iter()
+            .flat_map(Locale::names)
+            .find_map(|x| self.0.get(&format!("{}[{}]", key, x)))
+            .or_else(|| self.0.get(key))
+            .map(String::as_str)
+            .filter(|x| !x.is_empty())
+    }
+}
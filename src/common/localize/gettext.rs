@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::Path;
+
+const MAGIC: u32 = 0x9504_12DE;
+
+// A compiled message catalog in the GNU gettext format. Contexts and plural
+// forms are not supported, since they are not needed by this crate.
+//
+// https://www.gnu.org/software/gettext/manual/html_node/MO-Files.html
+pub(super) struct Catalog {
+    data: Vec<u8>,
+    big_endian: bool,
+}
+
+impl Catalog {
+    pub(super) fn open(path: &Path) -> Option<Self> {
+        Self::new(fs::read(path).ok()?)
+    }
+
+    pub(super) fn new(data: Vec<u8>) -> Option<Self> {
+        let magic = data.get(..4)?;
+        let big_endian = if magic == MAGIC.to_be_bytes() {
+            true
+        } else if magic == MAGIC.to_le_bytes() {
+            false
+        } else {
+            return None;
+        };
+        Some(Self { data, big_endian })
+    }
+
+    fn read_usize(&self, offset: usize) -> Option<usize> {
+        let bytes = self.data.get(offset..offset.checked_add(4)?)?;
+        let bytes = bytes.try_into().expect("slice has the wrong length");
+        let value = if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+        value.try_into().ok()
+    }
+
+    fn string(&self, table: usize, index: usize) -> Option<&[u8]> {
+        let entry = table.checked_add(index.checked_mul(8)?)?;
+        let len = self.read_usize(entry)?;
+        let offset = self.read_usize(entry.checked_add(4)?)?;
+        self.data.get(offset..offset.checked_add(len)?)
+    }
+
+    pub(super) fn translate(&self, message: &[u8]) -> Option<&[u8]> {
+        let count = self.read_usize(8)?;
+        let originals = self.read_usize(12)?;
+        let translations = self.read_usize(16)?;
+
+        (0..count)
+            .map_while(|i| self.string(originals, i).map(|x| (i, x)))
+            .find(|&(_, x)| x == message)
+            .and_then(|(i, _)| self.string(translations, i))
+            .filter(|x| !x.is_empty())
+    }
+}
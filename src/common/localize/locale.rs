@@ -0,0 +1,73 @@
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use std::ffi::OsStr;
+
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use crate::EnvProvider;
+
+// A POSIX locale name, such as "de_DE.UTF-8@euro". The encoding is ignored,
+// since it does not affect which translations should be used. Language tags,
+// such as "de-DE", are also accepted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Locale {
+    language: String,
+    country: Option<String>,
+    modifier: Option<String>,
+}
+
+impl Locale {
+    // Returns [None] for the "C" and "POSIX" locales, which do not have
+    // translations.
+    pub(super) fn parse(name: &str) -> Option<Self> {
+        let (name, modifier) = match name.split_once('@') {
+            Some((name, modifier)) => (name, Some(modifier)),
+            None => (name, None),
+        };
+        let name = name.split_once('.').map_or(name, |(name, _)| name);
+        let (language, country) = match name.split_once(['_', '-']) {
+            Some((language, country)) => (language, Some(country)),
+            None => (name, None),
+        };
+        if language.is_empty() || matches!(language, "C" | "POSIX") {
+            return None;
+        }
+
+        let non_empty = |x: &str| (!x.is_empty()).then(|| x.to_owned());
+        Some(Self {
+            language: language.to_owned(),
+            country: country.and_then(non_empty),
+            modifier: modifier.and_then(non_empty),
+        })
+    }
+
+    // Reads the locale used for messages, in the same order as setlocale.
+    #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+    pub(super) fn from_env<E>(env: &E) -> Option<Self>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|x| env.var(OsStr::new(x)))
+            .find(|x| !x.is_empty())
+            .and_then(|x| x.to_str().and_then(Self::parse))
+    }
+
+    // Returns the names that translations for this locale can be stored
+    // under, from most to least specific. This order is defined by the
+    // Desktop Entry Specification, but it is also used by gettext.
+    pub(super) fn names(&self) -> Vec<String> {
+        let language = &self.language;
+        let mut names = Vec::with_capacity(4);
+        if let Some(country) = &self.country {
+            if let Some(modifier) = &self.modifier {
+                names.push(format!("{}_{}@{}", language, country, modifier));
+            }
+            names.push(format!("{}_{}", language, country));
+        }
+        if let Some(modifier) = &self.modifier {
+            names.push(format!("{}@{}", language, modifier));
+        }
+        names.push(language.clone());
+        names
+    }
+}
@@ -1,10 +1,15 @@
 #![expect(clashing_extern_declarations)]
 
+use std::io;
 use std::ops::Deref;
 use std::os::raw::c_char;
+use std::path::PathBuf;
 use std::slice;
 use std::str;
 
+use crate::EnvProvider;
+use crate::KnownFolder;
+
 #[macro_use]
 mod fruity;
 use fruity::foundation::NSString;
@@ -123,3 +128,40 @@ pub(super) fn name(path: &str) -> String {
 
     unsafe { objc_retain(objc_msgSend(&obj, sel, path)) }.to_string()
 }
+
+pub(super) fn known_folder<E>(
+    folder: KnownFolder,
+    env: &E,
+) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    let path = match folder {
+        KnownFolder::Cache => "Library/Caches",
+        KnownFolder::Config | KnownFolder::Data => {
+            "Library/Application Support"
+        }
+        KnownFolder::Desktop => "Desktop",
+        KnownFolder::Documents => "Documents",
+        KnownFolder::Downloads => "Downloads",
+        KnownFolder::Home => "",
+        KnownFolder::Music => "Music",
+        KnownFolder::Pictures => "Pictures",
+        KnownFolder::Public => "Public",
+        KnownFolder::Templates => {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "templates folder is not defined on this platform",
+            ));
+        }
+        KnownFolder::Videos => "Movies",
+    };
+    env.home_dir()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "home directory is unknown",
+            )
+        })
+        .map(|x| if path.is_empty() { x } else { x.join(path) })
+}
@@ -0,0 +1,470 @@
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str;
+use std::sync::OnceLock;
+
+use super::locale::Locale;
+
+const DIRECTORY_TYPE: &str = "inode/directory";
+
+const MAGIC_HEADER: &[u8] = b"MIME-Magic\0\n";
+
+// Matches a file name against a pattern, which can contain the `*`, `?`, and
+// `[...]` wildcards supported by fnmatch.
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    let Some((&first, pattern_rest)) = pattern.split_first() else {
+        return name.is_empty();
+    };
+    if first == '*' {
+        return (0..=name.len())
+            .any(|i| glob_matches(pattern_rest, &name[i..]));
+    }
+    let Some((&ch, name_rest)) = name.split_first() else {
+        return false;
+    };
+    match first {
+        '?' => glob_matches(pattern_rest, name_rest),
+        '[' => {
+            let Some(end) = pattern.iter().skip(2).position(|&x| x == ']')
+            else {
+                return first == ch && glob_matches(pattern_rest, name_rest);
+            };
+            let mut class = &pattern[1..end + 2];
+            let negated = matches!(class.first(), Some('!' | '^'));
+            if negated {
+                class = &class[1..];
+            }
+            let mut matched = false;
+            let mut i = 0;
+            while i < class.len() {
+                if class.get(i + 1) == Some(&'-') && i + 2 < class.len() {
+                    matched |= (class[i]..=class[i + 2]).contains(&ch);
+                    i += 3;
+                } else {
+                    matched |= class[i] == ch;
+                    i += 1;
+                }
+            }
+            matched != negated && glob_matches(&pattern[end + 3..], name_rest)
+        }
+        _ => first == ch && glob_matches(pattern_rest, name_rest),
+    }
+}
+
+struct Glob {
+    weight: u32,
+    mime_type: String,
+    pattern: Vec<char>,
+    case_sensitive: bool,
+}
+
+// Parses a "globs2" file, which contains lines such as
+// `50:text/plain:*.txt:cs`.
+fn parse_globs(contents: &str) -> impl Iterator<Item = Glob> + '_ {
+    contents.lines().filter_map(|line| {
+        if line.starts_with('#') {
+            return None;
+        }
+        let mut fields = line.split(':');
+        let weight = fields.next()?.parse().ok()?;
+        let mime_type = fields.next()?;
+        if mime_type == "__NOCLOBBER__" {
+            return None;
+        }
+        let pattern = fields.next()?;
+        let case_sensitive = fields
+            .next()
+            .is_some_and(|flags| flags.split(',').any(|x| x == "cs"));
+        Some(Glob {
+            weight,
+            mime_type: mime_type.to_owned(),
+            pattern: if case_sensitive {
+                pattern.chars().collect()
+            } else {
+                pattern.chars().flat_map(char::to_lowercase).collect()
+            },
+            case_sensitive,
+        })
+    })
+}
+
+struct Matchlet {
+    indent: usize,
+    offset: usize,
+    value: Vec<u8>,
+    mask: Option<Vec<u8>>,
+    range: usize,
+}
+
+impl Matchlet {
+    fn matches(&self, data: &[u8]) -> bool {
+        (self.offset..self.offset.saturating_add(self.range)).any(|start| {
+            let Some(data) = start
+                .checked_add(self.value.len())
+                .and_then(|end| data.get(start..end))
+            else {
+                return false;
+            };
+            match &self.mask {
+                Some(mask) => data
+                    .iter()
+                    .zip(&self.value)
+                    .zip(mask)
+                    .all(|((x, y), mask)| x & mask == y & mask),
+                None => data == self.value,
+            }
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.offset
+            .saturating_add(self.range)
+            .saturating_add(self.value.len())
+    }
+}
+
+// Returns true if any matchlet in `matchlets` with the given indent matches,
+// along with one of its children if it has any.
+fn matchlets_match(
+    matchlets: &[Matchlet],
+    indent: usize,
+    data: &[u8],
+) -> bool {
+    let mut matchlets = matchlets;
+    while let Some((matchlet, rest)) = matchlets.split_first() {
+        let children_len =
+            rest.iter().take_while(|x| x.indent > indent).count();
+        let (children, rest) = rest.split_at(children_len);
+        if matchlet.matches(data)
+            && (children.is_empty()
+                || matchlets_match(children, indent + 1, data))
+        {
+            return true;
+        }
+        matchlets = rest;
+    }
+    false
+}
+
+struct MagicSection {
+    priority: u32,
+    mime_type: String,
+    matchlets: Vec<Matchlet>,
+}
+
+struct MagicParser<'a>(&'a [u8]);
+
+impl MagicParser<'_> {
+    fn next_byte_if(&mut self, byte: u8) -> bool {
+        if let Some(rest) = self.0.strip_prefix(&[byte]) {
+            self.0 = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_bytes(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.0.get(..len)?;
+        self.0 = &self.0[len..];
+        Some(bytes)
+    }
+
+    fn next_number(&mut self) -> Option<usize> {
+        let len = self.0.iter().take_while(|x| x.is_ascii_digit()).count();
+        str::from_utf8(self.next_bytes(len)?).ok()?.parse().ok()
+    }
+
+    fn next_matchlet(&mut self) -> Option<Matchlet> {
+        let indent = self.next_number().unwrap_or(0);
+        if !self.next_byte_if(b'>') {
+            return None;
+        }
+        let offset = self.next_number()?;
+        if !self.next_byte_if(b'=') {
+            return None;
+        }
+        let len = self.next_bytes(2)?;
+        let len = u16::from_be_bytes([len[0], len[1]]).into();
+        let mut value = self.next_bytes(len)?.to_owned();
+        let mut mask = if self.next_byte_if(b'&') {
+            Some(self.next_bytes(len)?.to_owned())
+        } else {
+            None
+        };
+        let word_size = if self.next_byte_if(b'~') {
+            self.next_number()?
+        } else {
+            1
+        };
+        let range = if self.next_byte_if(b'+') {
+            self.next_number()?
+        } else {
+            1
+        };
+        if !self.next_byte_if(b'\n') {
+            return None;
+        }
+
+        // Values for multi-byte words are stored in big-endian order but
+        // should be compared in the native order.
+        if cfg!(target_endian = "little") && word_size > 1 {
+            for bytes in
+                [Some(&mut value), mask.as_mut()].into_iter().flatten()
+            {
+                bytes.chunks_mut(word_size).for_each(<[_]>::reverse);
+            }
+        }
+        Some(Matchlet {
+            indent,
+            offset,
+            value,
+            mask,
+            range,
+        })
+    }
+
+    fn next_section(&mut self) -> Option<MagicSection> {
+        if !self.next_byte_if(b'[') {
+            return None;
+        }
+        let priority = self.next_number()?;
+        if !self.next_byte_if(b':') {
+            return None;
+        }
+        let len = self.0.iter().position(|&x| x == b']')?;
+        let mime_type = str::from_utf8(self.next_bytes(len)?).ok()?.to_owned();
+        let _ = self.next_bytes(1);
+        if !self.next_byte_if(b'\n') {
+            return None;
+        }
+
+        let mut matchlets = Vec::new();
+        while !self.0.is_empty() && !self.0.starts_with(b"[") {
+            matchlets.push(self.next_matchlet()?);
+        }
+        Some(MagicSection {
+            priority: priority.try_into().ok()?,
+            mime_type,
+            matchlets,
+        })
+    }
+}
+
+// Parses a "magic" file, which contains rules for detecting types from the
+// contents of files.
+fn parse_magic(contents: &[u8]) -> Option<Vec<MagicSection>> {
+    let mut parser = MagicParser(contents.strip_prefix(MAGIC_HEADER)?);
+    let mut sections = Vec::new();
+    while !parser.0.is_empty() {
+        sections.push(parser.next_section()?);
+    }
+    Some(sections)
+}
+
+fn unescape(value: &str) -> Option<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut value = value;
+    while let Some(start) = value.find('&') {
+        result.push_str(&value[..start]);
+        value = &value[start + 1..];
+        let end = value.find(';')?;
+        let ch = match &value[..end] {
+            "amp" => '&',
+            "apos" => '\'',
+            "gt" => '>',
+            "lt" => '<',
+            "quot" => '"',
+            entity => {
+                let code = entity.strip_prefix('#')?;
+                let code = match code.strip_prefix('x') {
+                    Some(code) => u32::from_str_radix(code, 16),
+                    None => code.parse(),
+                };
+                char::from_u32(code.ok()?)?
+            }
+        };
+        result.push(ch);
+        value = &value[end + 1..];
+    }
+    result.push_str(value);
+    Some(result)
+}
+
+// Returns the "comment" elements in the XML file describing a type. Each
+// contains the value of its "xml:lang" attribute, if any, and its text.
+fn parse_comments(contents: &str) -> Vec<(Option<&str>, String)> {
+    let mut comments = Vec::new();
+    let mut contents = contents;
+    while let Some(start) = contents.find("<comment") {
+        contents = &contents[start + "<comment".len()..];
+        let Some(end) = contents.find('>') else {
+            break;
+        };
+        let attributes = &contents[..end];
+        contents = &contents[end + 1..];
+        if !attributes.is_empty()
+            && !attributes.starts_with(char::is_whitespace)
+        {
+            continue;
+        }
+        let Some(end) = contents.find("</comment>") else {
+            break;
+        };
+        let language =
+            attributes.split_once("xml:lang=").and_then(|(_, x)| {
+                let quote =
+                    x.chars().next().filter(|x| matches!(x, '"' | '\''))?;
+                x[1..].split(quote).next()
+            });
+        if let Some(text) = unescape(&contents[..end]) {
+            comments.push((language, text));
+        }
+        contents = &contents[end..];
+    }
+    comments
+}
+
+// Finds the types of files using the shared-mime-info database, which is
+// stored in the "mime" subdirectory of each data directory.
+//
+// https://specifications.freedesktop.org/shared-mime-info-spec/latest/
+//
+// The files are only parsed the first time that they are needed, since they
+// can be large.
+pub(super) struct Database {
+    dirs: Vec<PathBuf>,
+    globs: OnceLock<Vec<Glob>>,
+    magic: OnceLock<Vec<MagicSection>>,
+}
+
+impl Database {
+    // The directories should be given in order of precedence.
+    pub(super) fn new(dirs: Vec<PathBuf>) -> Self {
+        Self {
+            dirs,
+            globs: OnceLock::new(),
+            magic: OnceLock::new(),
+        }
+    }
+
+    fn globs(&self) -> &[Glob] {
+        self.globs.get_or_init(|| {
+            self.dirs
+                .iter()
+                .filter_map(|x| fs::read_to_string(x.join("globs2")).ok())
+                .flat_map(|x| parse_globs(&x).collect::<Vec<_>>())
+                .collect()
+        })
+    }
+
+    fn magic(&self) -> &[MagicSection] {
+        self.magic.get_or_init(|| {
+            self.dirs
+                .iter()
+                .filter_map(|x| fs::read(x.join("magic")).ok())
+                .filter_map(|x| parse_magic(&x))
+                .flatten()
+                .collect()
+        })
+    }
+
+    // Types from earlier directories are preferred when weights are equal.
+    fn glob_type(&self, name: &str) -> Option<String> {
+        let name: Vec<_> = name.chars().collect();
+        let lowercase_name: Vec<_> =
+            name.iter().flat_map(|x| x.to_lowercase()).collect();
+
+        let mut result: Option<&Glob> = None;
+        for glob in self.globs() {
+            let is_better = result.map_or(true, |x| {
+                (glob.weight, glob.pattern.len()) > (x.weight, x.pattern.len())
+            });
+            let name = if glob.case_sensitive {
+                &name
+            } else {
+                &lowercase_name
+            };
+            if is_better && glob_matches(&glob.pattern, name) {
+                result = Some(glob);
+            }
+        }
+        result.map(|x| x.mime_type.clone())
+    }
+
+    fn magic_type(&self, path: &Path) -> Option<String> {
+        let sections = self.magic();
+        let len = sections
+            .iter()
+            .flat_map(|x| &x.matchlets)
+            .map(Matchlet::len)
+            .max()?;
+
+        let mut data = Vec::new();
+        let _ = File::open(path)
+            .ok()?
+            .take(len.try_into().ok()?)
+            .read_to_end(&mut data)
+            .ok()?;
+        let mut result: Option<&MagicSection> = None;
+        for section in sections {
+            if result.map_or(true, |x| section.priority > x.priority)
+                && matchlets_match(&section.matchlets, 0, &data)
+            {
+                result = Some(section);
+            }
+        }
+        result.map(|x| x.mime_type.clone())
+    }
+
+    // Determines the type of a file using its name, falling back to its
+    // contents. Directories always have the same type.
+    pub(super) fn mime_type(&self, path: &Path) -> Option<String> {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => {
+                return Some(DIRECTORY_TYPE.to_owned());
+            }
+            Ok(metadata) if !metadata.is_file() => return None,
+            _ => {}
+        }
+        path.file_name()
+            .and_then(|x| x.to_str())
+            .and_then(|x| self.glob_type(x))
+            .or_else(|| self.magic_type(path))
+    }
+
+    // Returns the description of a type, using the most specific locale name
+    // that has a value.
+    pub(super) fn comment(
+        &self,
+        mime_type: &str,
+        locale: Option<&Locale>,
+    ) -> Option<String> {
+        let (media, subtype) = mime_type.split_once('/')?;
+        if [media, subtype]
+            .iter()
+            .any(|x| x.is_empty() || x.starts_with('.') || x.contains('/'))
+        {
+            return None;
+        }
+
+        let locale_names = locale.map(Locale::names).unwrap_or_default();
+        self.dirs.iter().find_map(|dir| {
+            let path = dir.join(media).join(format!("{}.xml", subtype));
+            let contents = fs::read_to_string(path).ok()?;
+            let mut comments = parse_comments(&contents);
+            locale_names
+                .iter()
+                .map(|x| Some(&**x))
+                .chain([None])
+                .find_map(|name| {
+                    let index = comments.iter().position(|x| x.0 == name)?;
+                    Some(comments.swap_remove(index).1)
+                })
+                .filter(|x| !x.is_empty())
+        })
+    }
+}
@@ -1,18 +1,149 @@
 use std::ffi::OsString;
+use std::io;
 use std::path::Path;
+use std::path::PathBuf;
+
+use crate::error::LocalizeError;
+use crate::EnvProvider;
+use crate::KnownFolder;
 
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 mod macos;
 
-#[cfg_attr(
-    not(any(target_os = "ios", target_os = "macos")),
-    expect(unused_variables)
-)]
-pub(crate) fn name(path: &Path) -> Option<OsString> {
-    // Only UTF-8 paths can be localized on MacOS.
+#[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+mod desktop_entry;
+#[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+mod gettext;
+#[cfg(unix)]
+mod locale;
+#[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+mod mime;
+#[cfg(unix)]
+use locale::Locale;
+#[cfg(unix)]
+mod strings;
+#[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+mod xdg;
+
+// Localizes names using state that can be reused for multiple paths.
+pub(crate) struct Localizer {
+    // [None] if the language of the system should be used.
     #[cfg(any(target_os = "ios", target_os = "macos"))]
-    if let Some(path) = path.to_str() {
-        return Some(macos::name(path).into());
+    locale: Option<Option<Locale>>,
+    #[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+    inner: xdg::Localizer,
+}
+
+impl Localizer {
+    #[cfg_attr(
+        not(all(unix, not(any(target_os = "ios", target_os = "macos")))),
+        expect(unused_variables)
+    )]
+    pub(crate) fn new<E>(env: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        Self {
+            #[cfg(any(target_os = "ios", target_os = "macos"))]
+            locale: None,
+            #[cfg(all(
+                unix,
+                not(any(target_os = "ios", target_os = "macos"))
+            ))]
+            inner: xdg::Localizer::new(Locale::from_env(env), env),
+        }
+    }
+
+    #[cfg_attr(
+        not(all(unix, not(any(target_os = "ios", target_os = "macos")))),
+        expect(unused_variables)
+    )]
+    pub(crate) fn with_locale<E>(locale: &str, env: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        Self {
+            #[cfg(any(target_os = "ios", target_os = "macos"))]
+            locale: Some(Locale::parse(locale)),
+            #[cfg(all(
+                unix,
+                not(any(target_os = "ios", target_os = "macos"))
+            ))]
+            inner: xdg::Localizer::new(Locale::parse(locale), env),
+        }
+    }
+
+    // Returns `Ok(None)` if there is no localized name for the path.
+    #[cfg_attr(not(unix), expect(unused_variables))]
+    pub(crate) fn name(
+        &self,
+        path: &Path,
+    ) -> Result<Option<OsString>, LocalizeError> {
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        {
+            // The system API can only use the language of the system.
+            if let Some(locale) = &self.locale {
+                return Ok(locale
+                    .as_ref()
+                    .and_then(|x| strings::bundle_name(path, x))
+                    .map(Into::into));
+            }
+
+            // Only UTF-8 paths can be localized on MacOS.
+            path.to_str()
+                .map(|x| Some(macos::name(x).into()))
+                .ok_or(LocalizeError::InvalidPath)
+        }
+        #[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+        {
+            Ok(self.inner.name(path))
+        }
+        #[cfg(not(unix))]
+        {
+            Err(LocalizeError::Unsupported)
+        }
+    }
+
+    #[cfg_attr(
+        not(all(unix, not(any(target_os = "ios", target_os = "macos")))),
+        expect(unused_variables)
+    )]
+    pub(crate) fn type_name(&self, path: &Path) -> Option<OsString> {
+        #[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+        {
+            self.inner.type_name(path)
+        }
+        #[cfg(not(all(
+            unix,
+            not(any(target_os = "ios", target_os = "macos"))
+        )))]
+        {
+            None
+        }
+    }
+}
+
+#[cfg_attr(not(unix), expect(unused_variables))]
+pub(crate) fn known_folder<E>(
+    folder: KnownFolder,
+    env: &E,
+) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    {
+        macos::known_folder(folder, env)
+    }
+    #[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+    {
+        xdg::known_folder(folder, env)
+    }
+    #[cfg(not(unix))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "known folders are not supported on this platform",
+        ))
     }
-    None
 }
@@ -0,0 +1,177 @@
+use std::char;
+use std::collections::HashMap;
+use std::fs;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str;
+use std::str::Chars;
+
+use super::locale::Locale;
+
+const BUNDLE_EXTENSION: &str = ".localized";
+
+fn decode_utf16<F>(data: &[u8], from_bytes: F) -> Option<String>
+where
+    F: Fn([u8; 2]) -> u16,
+{
+    if data.len() % 2 != 0 {
+        return None;
+    }
+    char::decode_utf16(
+        data.chunks_exact(2).map(|x| {
+            from_bytes(x.try_into().expect("chunk has wrong length"))
+        }),
+    )
+    .collect::<Result<_, _>>()
+    .ok()
+}
+
+// Files can be encoded using UTF-8 or UTF-16, but UTF-16 files must start
+// with a byte order mark.
+fn decode(data: &[u8]) -> Option<String> {
+    if let Some(data) = data.strip_prefix(b"\xFF\xFE") {
+        decode_utf16(data, u16::from_le_bytes)
+    } else if let Some(data) = data.strip_prefix(b"\xFE\xFF") {
+        decode_utf16(data, u16::from_be_bytes)
+    } else {
+        let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+        str::from_utf8(data).ok().map(str::to_owned)
+    }
+}
+
+struct Parser<'a>(Peekable<Chars<'a>>);
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) -> Option<()> {
+        loop {
+            match self.0.peek() {
+                Some(ch) if ch.is_whitespace() => {
+                    let _ = self.0.next();
+                }
+                Some('/') => {
+                    let _ = self.0.next();
+                    match self.0.next()? {
+                        '/' => {
+                            while self.0.next_if(|&x| x != '\n').is_some() {}
+                        }
+                        '*' => {
+                            let mut last = None;
+                            while last != Some('*')
+                                || self.0.peek() != Some(&'/')
+                            {
+                                last = Some(self.0.next()?);
+                            }
+                            let _ = self.0.next();
+                        }
+                        _ => return None,
+                    }
+                }
+                _ => return Some(()),
+            }
+        }
+    }
+
+    fn next_escaped(&mut self) -> Option<char> {
+        let ch = match self.0.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'U' | 'u' => {
+                let code = (0..4)
+                    .map(|_| self.0.next()?.to_digit(16))
+                    .try_fold(0, |code, digit| Some(code * 16 + digit?))?;
+                char::from_u32(code)?
+            }
+            ch => ch,
+        };
+        Some(ch)
+    }
+
+    // Parses a quoted string or an unquoted word.
+    fn next_string(&mut self) -> Option<String> {
+        self.skip_whitespace()?;
+        let mut result = String::new();
+        if self.0.next_if_eq(&'"').is_some() {
+            loop {
+                let ch = match self.0.next()? {
+                    '"' => break,
+                    '\\' => self.next_escaped()?,
+                    ch => ch,
+                };
+                result.push(ch);
+            }
+        } else {
+            while let Some(ch) = self.0.next_if(|&x| {
+                x.is_alphanumeric()
+                    || matches!(x, '$' | '-' | '.' | '/' | ':' | '_')
+            }) {
+                result.push(ch);
+            }
+            if result.is_empty() {
+                return None;
+            }
+        }
+        Some(result)
+    }
+
+    fn next_symbol(&mut self, symbol: char) -> Option<bool> {
+        self.skip_whitespace()?;
+        Some(self.0.next_if_eq(&symbol).is_some())
+    }
+}
+
+// Parses a ".strings" file written for MacOS, which contains entries such as
+// `"key" = "value";`.
+//
+// https://developer.apple.com/documentation/bundleresources/placing-content-in-a-bundle
+pub(super) struct Strings(HashMap<String, String>);
+
+impl Strings {
+    pub(super) fn open(path: &Path) -> Option<Self> {
+        Self::parse(&fs::read(path).ok()?)
+    }
+
+    pub(super) fn parse(contents: &[u8]) -> Option<Self> {
+        let contents = decode(contents)?;
+        let mut parser = Parser(contents.chars().peekable());
+
+        let mut entries = HashMap::new();
+        loop {
+            parser.skip_whitespace()?;
+            if parser.0.peek().is_none() {
+                break;
+            }
+
+            let key = parser.next_string()?;
+            // Entries without a value use the key as their value.
+            let value = if parser.next_symbol('=')? {
+                parser.next_string()?
+            } else {
+                key.clone()
+            };
+            if !parser.next_symbol(';')? {
+                return None;
+            }
+            let _ = entries.insert(key, value);
+        }
+        Some(Self(entries))
+    }
+
+    pub(super) fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+}
+
+// Returns the name of a directory localized by MacOS using a ".localized"
+// subdirectory. For example, "Foo.localized/.localized/de.strings" contains
+// the name of "Foo.localized" for the "de" locale, using "Foo" as its key.
+pub(super) fn bundle_name(path: &Path, locale: &Locale) -> Option<String> {
+    let key = path.file_name()?.to_str()?.strip_suffix(BUNDLE_EXTENSION)?;
+    let bundle = path.join(BUNDLE_EXTENSION);
+    locale.names().into_iter().find_map(|name| {
+        Strings::open(&bundle.join(format!("{}.strings", name)))?
+            .get(key)
+            .filter(|x| !x.is_empty())
+            .map(str::to_owned)
+    })
+}
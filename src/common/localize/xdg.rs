@@ -0,0 +1,313 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::ffi::OsStringExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::EnvProvider;
+use crate::KnownFolder;
+
+use super::desktop_entry::DesktopEntry;
+use super::gettext::Catalog;
+use super::locale::Locale;
+use super::mime;
+use super::strings;
+
+// The variables in "user-dirs.dirs" and the untranslated names that
+// xdg-user-dirs uses for their directories.
+const USER_DIR_NAMES: &[(&str, &str)] = &[
+    ("XDG_DESKTOP_DIR", "Desktop"),
+    ("XDG_DOCUMENTS_DIR", "Documents"),
+    ("XDG_DOWNLOAD_DIR", "Downloads"),
+    ("XDG_MUSIC_DIR", "Music"),
+    ("XDG_PICTURES_DIR", "Pictures"),
+    ("XDG_PUBLICSHARE_DIR", "Public"),
+    ("XDG_TEMPLATES_DIR", "Templates"),
+    ("XDG_VIDEOS_DIR", "Videos"),
+];
+
+fn home_dir<E>(env: &E) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    env.home_dir().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "home directory is unknown")
+    })
+}
+
+// Relative paths are invalid and should be ignored, according to the
+// specification.
+fn base_dir<E>(env: &E, name: &str, default: &str) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    if let Some(path) = env.var(OsStr::new(name)).map(PathBuf::from) {
+        if path.is_absolute() {
+            return Ok(path);
+        }
+    }
+    home_dir(env).map(|x| x.join(default))
+}
+
+pub(super) fn config_home<E>(env: &E) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    base_dir(env, "XDG_CONFIG_HOME", ".config")
+}
+
+fn unquote(value: &[u8]) -> Option<Vec<u8>> {
+    let value = value.strip_prefix(b"\"")?.strip_suffix(b"\"")?;
+    let mut result = Vec::with_capacity(value.len());
+    let mut bytes = value.iter();
+    while let Some(&byte) = bytes.next() {
+        result.push(if byte == b'\\' { *bytes.next()? } else { byte });
+    }
+    Some(result)
+}
+
+// Parses the "user-dirs.dirs" file in `config_home`. Each entry contains the
+// name of a variable, such as `XDG_DOCUMENTS_DIR`, and the directory assigned
+// to it.
+pub(super) fn user_dirs(
+    config_home: &Path,
+    home: &Path,
+) -> io::Result<Vec<(Vec<u8>, PathBuf)>> {
+    let contents = fs::read(config_home.join("user-dirs.dirs"))?;
+    Ok(contents
+        .split(|&x| x == b'\n')
+        .filter_map(|line| {
+            let line = line.trim_ascii();
+            if line.starts_with(b"#") {
+                return None;
+            }
+            let separator = line.iter().position(|&x| x == b'=')?;
+            let name = line[..separator].trim_ascii();
+            let value = unquote(line[separator + 1..].trim_ascii())?;
+
+            let path = if let Some(suffix) = value.strip_prefix(b"$HOME") {
+                match suffix {
+                    [] => home.to_owned(),
+                    [b'/', suffix @ ..] => {
+                        home.join(OsStr::from_bytes(suffix))
+                    }
+                    _ => return None,
+                }
+            } else if value.starts_with(b"/") {
+                OsStr::from_bytes(&value).into()
+            } else {
+                return None;
+            };
+            Some((name.to_owned(), path))
+        })
+        .collect())
+}
+
+fn user_dir<E>(
+    env: &E,
+    name: &str,
+    default: Option<&str>,
+) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    let home = home_dir(env)?;
+    let user_dirs = match user_dirs(&config_home(env)?, &home) {
+        Ok(user_dirs) => user_dirs,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => return Err(error),
+    };
+    user_dirs
+        .into_iter()
+        .rev()
+        .find(|(x, _)| x == name.as_bytes())
+        .map(|(_, path)| path)
+        .or_else(|| default.map(|x| home.join(x)))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("user directory is not configured: {}", name),
+            )
+        })
+}
+
+pub(super) fn known_folder<E>(
+    folder: KnownFolder,
+    env: &E,
+) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    match folder {
+        KnownFolder::Cache => base_dir(env, "XDG_CACHE_HOME", ".cache"),
+        KnownFolder::Config => config_home(env),
+        KnownFolder::Data => base_dir(env, "XDG_DATA_HOME", ".local/share"),
+        KnownFolder::Desktop => {
+            user_dir(env, "XDG_DESKTOP_DIR", Some("Desktop"))
+        }
+        KnownFolder::Documents => user_dir(env, "XDG_DOCUMENTS_DIR", None),
+        KnownFolder::Downloads => user_dir(env, "XDG_DOWNLOAD_DIR", None),
+        KnownFolder::Home => home_dir(env),
+        KnownFolder::Music => user_dir(env, "XDG_MUSIC_DIR", None),
+        KnownFolder::Pictures => user_dir(env, "XDG_PICTURES_DIR", None),
+        KnownFolder::Public => user_dir(env, "XDG_PUBLICSHARE_DIR", None),
+        KnownFolder::Templates => user_dir(env, "XDG_TEMPLATES_DIR", None),
+        KnownFolder::Videos => user_dir(env, "XDG_VIDEOS_DIR", None),
+    }
+}
+
+fn data_dirs<E>(env: &E) -> Vec<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    let dirs: Vec<_> = env
+        .var(OsStr::new("XDG_DATA_DIRS"))
+        .map(|x| {
+            x.as_bytes()
+                .split(|&x| x == b':')
+                .map(|x| PathBuf::from(OsStr::from_bytes(x)))
+                .filter(|x| x.is_absolute())
+                .collect()
+        })
+        .unwrap_or_default();
+    if dirs.is_empty() {
+        vec!["/usr/local/share".into(), "/usr/share".into()]
+    } else {
+        dirs
+    }
+}
+
+// Localizes names using the sources read by Linux desktop environments:
+// - ".directory" files written by KDE, which contain a localized name for
+//   their directory.
+// - the directories created by xdg-user-dirs, which are named using the
+//   locale in "user-dirs.locale" and can be translated to the current
+//   locale using its message catalog.
+// - ".localized" bundles written for MacOS, which can be found in
+//   directories copied from that platform.
+pub(super) struct Localizer {
+    locale: Option<Locale>,
+    data_dirs: Vec<PathBuf>,
+    user_dirs: Vec<(Vec<u8>, PathBuf)>,
+    user_dirs_locale: Option<Locale>,
+    catalogs: RefCell<HashMap<PathBuf, Option<Catalog>>>,
+    mime_database: mime::Database,
+}
+
+impl Localizer {
+    pub(super) fn new<E>(locale: Option<Locale>, env: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        let mut user_dirs = Vec::new();
+        let mut user_dirs_locale = None;
+        if let (Ok(config_home), Ok(home)) = (config_home(env), home_dir(env))
+        {
+            user_dirs =
+                self::user_dirs(&config_home, &home).unwrap_or_default();
+            user_dirs_locale =
+                fs::read_to_string(config_home.join("user-dirs.locale"))
+                    .ok()
+                    .and_then(|x| Locale::parse(x.trim()));
+        }
+
+        let data_dirs = data_dirs(env);
+        let mime_dirs = base_dir(env, "XDG_DATA_HOME", ".local/share")
+            .into_iter()
+            .chain(data_dirs.iter().cloned())
+            .map(|x| x.join("mime"))
+            .collect();
+
+        Self {
+            locale,
+            data_dirs,
+            user_dirs,
+            user_dirs_locale,
+            catalogs: RefCell::new(HashMap::new()),
+            mime_database: mime::Database::new(mime_dirs),
+        }
+    }
+
+    // Translates a message from the catalog of xdg-user-dirs. Untranslated
+    // messages are returned unchanged.
+    fn translate<'a>(
+        &self,
+        message: &'a str,
+        locale: Option<&Locale>,
+    ) -> Cow<'a, [u8]> {
+        locale
+            .into_iter()
+            .flat_map(Locale::names)
+            .flat_map(|name| {
+                self.data_dirs.iter().map(move |dir| {
+                    dir.join("locale")
+                        .join(&name)
+                        .join("LC_MESSAGES/xdg-user-dirs.mo")
+                })
+            })
+            .find_map(|path| {
+                // Catalogs are cached, since the same ones are usually needed
+                // for each directory.
+                self.catalogs
+                    .borrow_mut()
+                    .entry(path)
+                    .or_insert_with_key(|x| Catalog::open(x))
+                    .as_ref()?
+                    .translate(message.as_bytes())
+                    .map(|x| Cow::Owned(x.to_owned()))
+            })
+            .unwrap_or(Cow::Borrowed(message.as_bytes()))
+    }
+
+    fn directory_name(&self, path: &Path) -> Option<OsString> {
+        let contents = fs::read(path.join(".directory")).ok()?;
+        DesktopEntry::parse(&contents)?
+            .localized("Name", self.locale.as_ref())
+            .map(Into::into)
+    }
+
+    fn user_dir_name(&self, path: &Path) -> Option<OsString> {
+        let name = path.file_name()?;
+        let message =
+            USER_DIR_NAMES.iter().find_map(|&(variable, message)| {
+                self.user_dirs
+                    .iter()
+                    .rev()
+                    .find(|(x, _)| x == variable.as_bytes())
+                    .filter(|(_, x)| x == path)
+                    .map(|_| message)
+            })?;
+
+        // Renamed directories should not be translated.
+        if self.translate(message, self.user_dirs_locale.as_ref())
+            != name.as_bytes()
+        {
+            return None;
+        }
+        Some(OsString::from_vec(
+            self.translate(message, self.locale.as_ref()).into_owned(),
+        ))
+    }
+
+    pub(super) fn name(&self, path: &Path) -> Option<OsString> {
+        self.directory_name(path)
+            .or_else(|| self.user_dir_name(path))
+            .or_else(|| {
+                strings::bundle_name(path, self.locale.as_ref()?)
+                    .map(Into::into)
+            })
+    }
+
+    pub(super) fn type_name(&self, path: &Path) -> Option<OsString> {
+        let mime_type = self.mime_database.mime_type(path)?;
+        self.mime_database
+            .comment(&mime_type, self.locale.as_ref())
+            .map(Into::into)
+    }
+}
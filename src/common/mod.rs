@@ -1,8 +1,11 @@
 use std::borrow::Cow;
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use std::fs;
 use std::io;
 use std::path::Path;
 
 use crate::BasePathBuf;
+use crate::FileId;
 
 #[cfg(feature = "localization")]
 pub(super) mod localize;
@@ -31,6 +34,83 @@ pub(crate) fn shorten(path: &Path) -> io::Result<Cow<'_, Path>> {
     expand(path)
 }
 
+#[cfg(unix)]
+pub(crate) fn device_id(path: &Path) -> io::Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    path.metadata().map(|x| x.dev())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn device_id(_: &Path) -> io::Result<u64> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "device IDs are not supported on this platform",
+    ))
+}
+
+pub(crate) fn is_hidden(path: &Path) -> io::Result<bool> {
+    let metadata = path.symlink_metadata()?;
+
+    // The attribute is checked first, since it applies to files without a
+    // name.
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    {
+        #[cfg(target_os = "ios")]
+        use std::os::ios::fs::MetadataExt;
+        #[cfg(target_os = "macos")]
+        use std::os::macos::fs::MetadataExt;
+
+        const UF_HIDDEN: u32 = 0x8000;
+
+        if metadata.st_flags() & UF_HIDDEN != 0 {
+            return Ok(true);
+        }
+    }
+    #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+    let _ = metadata;
+
+    let Some(name) = path.file_name() else {
+        return Ok(false);
+    };
+    if name.as_encoded_bytes().starts_with(b".") {
+        return Ok(true);
+    }
+
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    {
+        Ok(false)
+    }
+    #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+    {
+        // File managers on Linux also hide the names listed in a ".hidden"
+        // file in the same directory.
+        let parent = path.parent().unwrap_or(path);
+        match fs::read(parent.join(".hidden")) {
+            Ok(contents) => Ok(contents
+                .split(|&x| x == b'\n')
+                .any(|x| x == name.as_encoded_bytes())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn file_id(path: &Path) -> io::Result<FileId> {
+    use std::os::unix::fs::MetadataExt;
+
+    path.metadata().map(|x| FileId::new(x.dev(), x.ino()))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn file_id(_: &Path) -> io::Result<FileId> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "file IDs are not supported on this platform",
+    ))
+}
+
 pub(crate) fn push(base: &mut BasePathBuf, path: &Path) {
     if !path.as_os_str().is_empty() {
         base.0.push(path);
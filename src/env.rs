@@ -0,0 +1,118 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// A source of environment information.
+///
+/// Methods that read the environment accept an implementation of this trait,
+/// which allows the environment to be replaced. [`SystemEnv`] should be used
+/// to read the environment of the current process.
+///
+/// Unlike other traits in this crate, this trait is not sealed. It is designed
+/// to be implemented by users.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::ffi::OsStr;
+/// use std::ffi::OsString;
+///
+/// use normpath::EnvProvider;
+///
+/// struct FakeEnv(HashMap<OsString, OsString>);
+///
+/// impl EnvProvider for FakeEnv {
+///     fn var(&self, name: &OsStr) -> Option<OsString> {
+///         self.0.get(name).cloned()
+///     }
+/// }
+/// ```
+pub trait EnvProvider {
+    /// Returns the value of an environment variable, or [`None`] if it is not
+    /// defined.
+    fn var(&self, name: &OsStr) -> Option<OsString>;
+
+    /// Returns the home directory of the current user.
+    ///
+    /// The default implementation reads the `USERPROFILE` variable on Windows
+    /// and the `HOME` variable on other platforms. Empty values are ignored.
+    #[inline]
+    fn home_dir(&self) -> Option<PathBuf> {
+        let name = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+        self.var(OsStr::new(name))
+            .filter(|x| !x.is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Returns the home directory of the user named `user`.
+    ///
+    /// The default implementation always returns [`None`].
+    #[inline]
+    fn user_home_dir(&self, user: &OsStr) -> Option<PathBuf> {
+        let _ = user;
+        None
+    }
+}
+
+impl<T> EnvProvider for &T
+where
+    T: EnvProvider + ?Sized,
+{
+    #[inline]
+    fn var(&self, name: &OsStr) -> Option<OsString> {
+        (**self).var(name)
+    }
+
+    #[inline]
+    fn home_dir(&self) -> Option<PathBuf> {
+        (**self).home_dir()
+    }
+
+    #[inline]
+    fn user_home_dir(&self, user: &OsStr) -> Option<PathBuf> {
+        (**self).user_home_dir(user)
+    }
+}
+
+/// The environment of the current process.
+///
+/// # Implementation
+///
+/// Currently, [`EnvProvider::user_home_dir`] reads "/etc/passwd" on Unix and
+/// is not supported on other platforms.
+///
+/// However, the implementation is subject to change. This section is only
+/// informative.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemEnv;
+
+impl EnvProvider for SystemEnv {
+    #[inline]
+    fn var(&self, name: &OsStr) -> Option<OsString> {
+        std::env::var_os(name)
+    }
+
+    #[cfg_attr(not(unix), expect(unused_variables))]
+    fn user_home_dir(&self, user: &OsStr) -> Option<PathBuf> {
+        #[cfg(unix)]
+        {
+            use std::fs;
+            use std::os::unix::ffi::OsStrExt;
+
+            let passwd = fs::read("/etc/passwd").ok()?;
+            passwd.split(|&x| x == b'\n').find_map(|line| {
+                let mut fields = line.split(|&x| x == b':');
+                if fields.next()? != user.as_bytes() {
+                    return None;
+                }
+                fields
+                    .nth(4)
+                    .filter(|x| !x.is_empty())
+                    .map(|x| OsStr::from_bytes(x).into())
+            })
+        }
+        #[cfg(not(unix))]
+        None
+    }
+}
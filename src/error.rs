@@ -1,12 +1,367 @@
 //! The error types defined by this crate.
 
 use std::error::Error;
+use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::path::Path;
 use std::path::PathBuf;
 
+/// The error returned when [`BasePath::join_archive_entry`] rejects an entry
+/// name.
+///
+/// [`BasePath::join_archive_entry`]: super::BasePath::join_archive_entry
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum EntryError {
+    /// The name starts with a separator.
+    Absolute,
+
+    /// The name has a component that starts with a drive letter, such as
+    /// `C:`.
+    DrivePrefix,
+
+    /// The name starts with two separators, which would be interpreted as a
+    /// UNC prefix on Windows.
+    UncPrefix,
+
+    /// The name has more `..` components than preceding normal components at
+    /// some point, so it would refer to a path outside of the base.
+    EscapesBase,
+
+    /// The name cannot be represented on this platform, such as a name that
+    /// is not valid UTF-8 on Windows.
+    InvalidEncoding,
+}
+
+impl Display for EntryError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absolute => "archive entry is absolute",
+            Self::DrivePrefix => "archive entry has a drive prefix",
+            Self::UncPrefix => "archive entry has a UNC prefix",
+            Self::EscapesBase => "archive entry escapes the base directory",
+            Self::InvalidEncoding => {
+                "archive entry cannot be represented on this platform"
+            }
+        }
+        .fmt(f)
+    }
+}
+
+impl Error for EntryError {}
+
+/// The error wrapped by the [`io::Error`] returned when
+/// [`BasePathBuf::expand_shell`] cannot expand a path.
+///
+/// [`BasePathBuf::expand_shell`]: super::BasePathBuf::expand_shell
+/// [`io::Error`]: ::std::io::Error
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ExpandError {
+    /// The path uses a variable that is not defined.
+    UndefinedVariable(OsString),
+
+    /// The path starts with `~user`, but the home directory of that user is
+    /// unknown.
+    UnknownUser(OsString),
+
+    /// The path starts with `~`, but the home directory is unknown.
+    MissingHome,
+
+    /// A variable reference is malformed, such as `${` without a closing
+    /// brace.
+    InvalidSyntax,
+}
+
+impl Display for ExpandError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedVariable(name) => write!(
+                f,
+                "environment variable is not defined: \"{}\"",
+                name.to_string_lossy(),
+            ),
+            Self::UnknownUser(user) => write!(
+                f,
+                "home directory is unknown for user: \"{}\"",
+                user.to_string_lossy(),
+            ),
+            Self::MissingHome => "home directory is unknown".fmt(f),
+            Self::InvalidSyntax => "variable reference is invalid".fmt(f),
+        }
+    }
+}
+
+impl Error for ExpandError {}
+
+/// The error returned when a path cannot be converted to or from a `file:` URL.
+///
+/// It is returned by [`BasePath::to_file_url`] and
+/// [`BasePathBuf::from_file_url`].
+///
+/// [`BasePath::to_file_url`]: super::BasePath::to_file_url
+/// [`BasePathBuf::from_file_url`]: super::BasePathBuf::from_file_url
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FileUrlError {
+    /// The URL does not use the `file:` scheme.
+    InvalidScheme,
+
+    /// The URL contains a `%` that is not followed by two hexadecimal digits.
+    InvalidPercentEncoding,
+
+    /// The URL has a host that cannot be represented on this platform.
+    InvalidHost,
+
+    /// The URL has a query or fragment.
+    QueryOrFragment,
+
+    /// The URL has a path that cannot be represented on this platform, such
+    /// as a path containing an encoded separator or null byte.
+    InvalidPath,
+
+    /// The path cannot be represented as a URL, such as a relative path or a
+    /// device path on Windows.
+    UnsupportedPath,
+}
+
+impl Display for FileUrlError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidScheme => "URL scheme is not \"file\"",
+            Self::InvalidPercentEncoding => "URL percent-encoding is invalid",
+            Self::InvalidHost => "URL host is not supported",
+            Self::QueryOrFragment => "URL has a query or fragment",
+            Self::InvalidPath => "URL path is not valid for this platform",
+            Self::UnsupportedPath => "path cannot be represented as a URL",
+        }
+        .fmt(f)
+    }
+}
+
+impl Error for FileUrlError {}
+
+/// A component of a path that is not valid for a [`Platform`].
+///
+/// It is reported by [`ValidationError`].
+///
+/// [`Platform`]: super::Platform
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidComponent {
+    pub(super) index: usize,
+    pub(super) name: OsString,
+    pub(super) kind: InvalidComponentKind,
+}
+
+impl InvalidComponent {
+    /// Returns the index of this component in the iterator returned by
+    /// [`BasePath::components`].
+    ///
+    /// [`BasePath::components`]: super::BasePath::components
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the reason that this component is invalid.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> InvalidComponentKind {
+        self.kind
+    }
+
+    /// Returns the name of this component.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+}
+
+impl Display for InvalidComponent {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" ", self.name.to_string_lossy())?;
+        match self.kind {
+            InvalidComponentKind::ReservedName => {
+                "is a reserved device name".fmt(f)
+            }
+            InvalidComponentKind::ForbiddenCharacter(ch) => {
+                write!(f, "contains a forbidden character: {:?}", ch)
+            }
+            InvalidComponentKind::ControlCharacter(ch) => {
+                write!(f, "contains a control character: {:?}", ch)
+            }
+            InvalidComponentKind::TrailingDotOrSpace => {
+                "ends with a period or space".fmt(f)
+            }
+            InvalidComponentKind::InvalidEncoding => {
+                "is not valid UTF-8".fmt(f)
+            }
+            InvalidComponentKind::TooLong => "is too long".fmt(f),
+        }
+    }
+}
+
+/// The reason that a component is not valid for a [`Platform`].
+///
+/// [`Platform`]: super::Platform
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum InvalidComponentKind {
+    /// The name refers to a legacy device on Windows, such as "CON" or
+    /// "COM1.txt".
+    ReservedName,
+
+    /// The name contains a character that cannot be used in file names, such
+    /// as `:` on Windows. The first such character is contained.
+    ForbiddenCharacter(char),
+
+    /// The name contains a control character, which cannot be used in file
+    /// names on Windows. The null character cannot be used on any platform.
+    /// The first such character is contained.
+    ControlCharacter(char),
+
+    /// The name ends with a period or space, which Windows removes.
+    TrailingDotOrSpace,
+
+    /// The name is not valid UTF-8, which is required by the file systems
+    /// used on MacOS.
+    InvalidEncoding,
+
+    /// The name is longer than 255 units in the encoding used by the
+    /// platform's file systems.
+    TooLong,
+}
+
+/// The error returned when a method that replaces the file name of a
+/// [`BasePath`] is given a value that is not a single file name.
+///
+/// Accepting other values could remove the [prefix] of the path.
+///
+/// [`BasePath`]: super::BasePath
+/// [prefix]: ::std::path::Prefix
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidFileNameError(pub(super) ());
+
+impl Display for InvalidFileNameError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        "value is not a single file name".fmt(f)
+    }
+}
+
+impl Error for InvalidFileNameError {}
+
+/// The error returned when [`RelPath::try_new`] is given a path that is not
+/// a valid relative path.
+///
+/// [`RelPath::try_new`]: super::RelPath::try_new
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidRelPathError(pub(super) ());
+
+impl Display for InvalidRelPathError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        "path is not relative or contains a `..` component".fmt(f)
+    }
+}
+
+impl Error for InvalidRelPathError {}
+
+/// The error returned when [`RelPathBuf::try_new`] is given a path that is
+/// not a valid relative path.
+///
+/// [`RelPathBuf::try_new`]: super::RelPathBuf::try_new
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidRelPathBufError(pub(super) PathBuf);
+
+impl InvalidRelPathBufError {
+    /// Returns a reference to the path that caused this error.
+    #[inline]
+    #[must_use]
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Returns the path that caused this error.
+    #[inline]
+    #[must_use]
+    pub fn into_path_buf(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl Display for InvalidRelPathBufError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "path is not relative or contains a `..` component: \"{}\"",
+            self.0.display(),
+        )
+    }
+}
+
+impl Error for InvalidRelPathBufError {}
+
+/// The error returned when [`PathExt::try_localize_name`] cannot localize a
+/// path.
+///
+/// [`PathExt::try_localize_name`]: super::PathExt::try_localize_name
+#[cfg(feature = "localization")]
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum LocalizeError {
+    /// The path is empty, so it has no name.
+    Empty,
+
+    /// The path ends with a `..` component, so its name is unknown without
+    /// accessing the file system.
+    ParentComponent,
+
+    /// The path cannot be given to the platform's localization API, such as
+    /// when it is not valid UTF-8 on MacOS or contains a null character on
+    /// Windows.
+    InvalidPath,
+
+    /// The platform's localization API returned an error, such as when the
+    /// path does not exist on Windows.
+    Failed,
+
+    /// Localization is not supported on this platform.
+    Unsupported,
+}
+
+#[cfg(feature = "localization")]
+impl Display for LocalizeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => "path is empty".fmt(f),
+            Self::ParentComponent => "path ends with a `..` component".fmt(f),
+            Self::InvalidPath => {
+                "path cannot be given to the localization API".fmt(f)
+            }
+            Self::Failed => "localization API returned an error".fmt(f),
+            Self::Unsupported => {
+                "localization is not supported on this platform".fmt(f)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "localization")]
+impl Error for LocalizeError {}
+
 /// The error returned when [`BasePath::try_new`] is given a path without a
 /// prefix.
 ///
@@ -70,3 +425,47 @@ impl Display for ParentError {
 }
 
 impl Error for ParentError {}
+
+/// The error returned when [`BasePath::validate_for`] finds components that
+/// are not valid for a platform.
+///
+/// [`BasePath::validate_for`]: super::BasePath::validate_for
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError(pub(super) Vec<InvalidComponent>);
+
+impl ValidationError {
+    /// Returns the invalid components, in the order that they appear in the
+    /// path.
+    ///
+    /// A component is included once for each reason that it is invalid.
+    #[inline]
+    #[must_use]
+    pub fn components(&self) -> &[InvalidComponent] {
+        &self.0
+    }
+
+    /// Equivalent to [`components`] but returns an owned vector.
+    ///
+    /// [`components`]: Self::components
+    #[inline]
+    #[must_use]
+    pub fn into_components(self) -> Vec<InvalidComponent> {
+        self.0
+    }
+}
+
+impl Display for ValidationError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        "path has invalid components: ".fmt(f)?;
+        for (i, component) in self.0.iter().enumerate() {
+            if i != 0 {
+                ", ".fmt(f)?;
+            }
+            component.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationError {}
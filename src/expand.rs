@@ -0,0 +1,149 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+
+use super::error::ExpandError;
+use super::lexical::os_str;
+use super::EnvProvider;
+
+/// The syntax used to expand variables in a path.
+///
+/// The [default] is the syntax most commonly used on the current platform.
+///
+/// [default]: Self::default
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ShellSyntax {
+    /// Variables are written as `$VAR` or `${VAR}`, and only `/` separates a
+    /// user name following `~` from the rest of the path.
+    Posix,
+
+    /// Variables are written as `%VAR%`, `%%` is a literal `%`, and both `/`
+    /// and `\` separate a user name following `~` from the rest of the path.
+    Windows,
+}
+
+impl ShellSyntax {
+    fn is_separator(self, byte: u8) -> bool {
+        match self {
+            Self::Posix => byte == b'/',
+            Self::Windows => [b'/', b'\\'].contains(&byte),
+        }
+    }
+}
+
+impl Default for ShellSyntax {
+    #[inline]
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Posix
+        }
+    }
+}
+
+fn is_posix_name_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn var<E>(env: &E, name: &[u8]) -> Result<OsString, ExpandError>
+where
+    E: EnvProvider + ?Sized,
+{
+    let name = os_str(name);
+    env.var(name)
+        .ok_or_else(|| ExpandError::UndefinedVariable(name.to_owned()))
+}
+
+// Returns the name and length of the variable reference following a `$`, if
+// there is one.
+fn posix_var(string: &[u8]) -> Result<Option<(&[u8], usize)>, ExpandError> {
+    if let Some(string) = string.strip_prefix(b"{") {
+        let end = string
+            .iter()
+            .position(|&x| x == b'}')
+            .ok_or(ExpandError::InvalidSyntax)?;
+        let name = &string[..end];
+        if name.is_empty() || !name.iter().all(|&x| is_posix_name_byte(x)) {
+            return Err(ExpandError::InvalidSyntax);
+        }
+        return Ok(Some((name, end + 2)));
+    }
+
+    if string.first().map_or(true, u8::is_ascii_digit) {
+        return Ok(None);
+    }
+    let end = string
+        .iter()
+        .position(|&x| !is_posix_name_byte(x))
+        .unwrap_or(string.len());
+    Ok((end != 0).then(|| (&string[..end], end)))
+}
+
+pub(super) fn expand<E>(
+    path: &OsStr,
+    syntax: ShellSyntax,
+    env: &E,
+) -> Result<OsString, ExpandError>
+where
+    E: EnvProvider + ?Sized,
+{
+    let mut string = path.as_encoded_bytes();
+    let mut result = OsString::with_capacity(string.len());
+
+    if let Some(suffix) = string.strip_prefix(b"~") {
+        let end = suffix
+            .iter()
+            .position(|&x| syntax.is_separator(x))
+            .unwrap_or(suffix.len());
+        let (user, suffix) = suffix.split_at(end);
+        let home = if user.is_empty() {
+            env.home_dir().ok_or(ExpandError::MissingHome)?
+        } else {
+            let user = os_str(user);
+            env.user_home_dir(user)
+                .ok_or_else(|| ExpandError::UnknownUser(user.to_owned()))?
+        };
+        result.push(home);
+        string = suffix;
+    }
+
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i < string.len() {
+        let (value, length) = match (syntax, string[i]) {
+            (ShellSyntax::Posix, b'$') => {
+                let Some((name, length)) = posix_var(&string[i + 1..])? else {
+                    i += 1;
+                    continue;
+                };
+                (var(env, name)?, length + 1)
+            }
+            (ShellSyntax::Windows, b'%') => {
+                let Some(end) =
+                    string[i + 1..].iter().position(|&x| x == b'%')
+                else {
+                    break;
+                };
+                let name = &string[i + 1..i + 1 + end];
+                let value = if name.is_empty() {
+                    "%".into()
+                } else {
+                    var(env, name)?
+                };
+                (value, end + 2)
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        result.push(os_str(&string[literal_start..i]));
+        result.push(value);
+        i += length;
+        literal_start = i;
+    }
+    result.push(os_str(&string[literal_start..]));
+
+    Ok(result)
+}
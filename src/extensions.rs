@@ -0,0 +1,96 @@
+use std::ffi::OsStr;
+use std::iter::FusedIterator;
+
+use super::lexical;
+
+// Splits a file name into its prefix and the extensions after it, not
+// including the separating period. A leading period is part of the prefix,
+// so names like ".bashrc" have no extensions.
+pub(super) fn split_file_prefix(name: &[u8]) -> (&[u8], Option<&[u8]>) {
+    if let Some(index) = name.iter().skip(1).position(|&x| x == b'.') {
+        let index = index + 1;
+        (&name[..index], Some(&name[index + 1..]))
+    } else {
+        (name, None)
+    }
+}
+
+// Returns the length of the file name after removing up to `count`
+// extensions.
+pub(super) fn strip_extensions(name: &[u8], mut count: usize) -> usize {
+    let prefix_len = split_file_prefix(name).0.len();
+    let mut len = name.len();
+    while count > 0 && len > prefix_len {
+        len = name[..len]
+            .iter()
+            .rposition(|&x| x == b'.')
+            .expect("missing period before extension");
+        count -= 1;
+    }
+    len
+}
+
+pub(super) fn has_extension_ci(name: &[u8], extension: &[u8]) -> bool {
+    let Some(extensions) = split_file_prefix(name).1 else {
+        return false;
+    };
+    if extension.is_empty() || extensions.len() < extension.len() {
+        return false;
+    }
+
+    let index = extensions.len() - extension.len();
+    extensions[index..].eq_ignore_ascii_case(extension)
+        && (index == 0 || extensions[index - 1] == b'.')
+}
+
+/// An iterator over the extensions of a file name.
+///
+/// This struct is created by [`BasePath::extensions`]. See its documentation
+/// for more information.
+///
+/// [`BasePath::extensions`]: super::BasePath::extensions
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Extensions<'a>(Option<&'a [u8]>);
+
+impl<'a> Extensions<'a> {
+    pub(super) fn new(name: Option<&'a OsStr>) -> Self {
+        Self(name.and_then(|x| split_file_prefix(x.as_encoded_bytes()).1))
+    }
+}
+
+impl DoubleEndedIterator for Extensions<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let extensions = self.0?;
+        let extension =
+            if let Some(index) = extensions.iter().position(|&x| x == b'.') {
+                self.0 = Some(&extensions[index + 1..]);
+                &extensions[..index]
+            } else {
+                self.0 = None;
+                extensions
+            };
+        Some(lexical::os_str(extension))
+    }
+}
+
+impl FusedIterator for Extensions<'_> {}
+
+impl<'a> Iterator for Extensions<'a> {
+    type Item = &'a OsStr;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let extensions = self.0?;
+        let extension =
+            if let Some(index) = extensions.iter().rposition(|&x| x == b'.') {
+                self.0 = Some(&extensions[..index]);
+                &extensions[index + 1..]
+            } else {
+                self.0 = None;
+                extensions
+            };
+        Some(lexical::os_str(extension))
+    }
+}
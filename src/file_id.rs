@@ -0,0 +1,30 @@
+/// An identifier for a file, which is unique while the file exists.
+///
+/// Identifiers are equal for all paths that refer to the same file, such as
+/// through hard links, symbolic links, or bind mounts. They can be hashed to
+/// remove duplicate files from a set.
+///
+/// This struct is created by [`BasePath::file_id`].
+///
+/// # Implementation
+///
+/// Currently, this struct contains:
+/// - the device ID and inode number on Unix platforms.
+/// - the volume serial number and file index on Windows.
+///
+/// However, the implementation is subject to change. This section is only
+/// informative.
+///
+/// [`BasePath::file_id`]: super::BasePath::file_id
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FileId {
+    device: u64,
+    index: u64,
+}
+
+impl FileId {
+    #[cfg_attr(not(any(unix, windows)), expect(dead_code))]
+    pub(crate) const fn new(device: u64, index: u64) -> Self {
+        Self { device, index }
+    }
+}
@@ -0,0 +1,136 @@
+use std::io;
+
+use super::imp::localize;
+use super::BasePathBuf;
+use super::EnvProvider;
+use super::SystemEnv;
+
+/// A directory that has a standard purpose for the current user.
+///
+/// The directories returned for each variant are described by
+/// [`known_folder`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum KnownFolder {
+    /// The directory for user-specific non-essential data files.
+    Cache,
+
+    /// The directory for user-specific configuration files.
+    Config,
+
+    /// The directory for user-specific data files.
+    Data,
+
+    /// The desktop directory.
+    Desktop,
+
+    /// The directory for documents.
+    Documents,
+
+    /// The directory for downloaded files.
+    Downloads,
+
+    /// The home directory.
+    Home,
+
+    /// The directory for music.
+    Music,
+
+    /// The directory for pictures.
+    Pictures,
+
+    /// The directory for files shared with other users.
+    Public,
+
+    /// The directory for templates of new files.
+    Templates,
+
+    /// The directory for videos.
+    Videos,
+}
+
+/// Returns the path of a known folder for the current user.
+///
+/// The path is not guaranteed to exist.
+///
+/// # Implementation
+///
+/// Currently, this function uses:
+/// - [`SHGetKnownFolderPath`] on Windows.
+/// - the standard directories in the home directory on MacOS, such as
+///   "~/Library/Caches" for [`KnownFolder::Cache`].
+/// - the [XDG Base Directory Specification] and the "user-dirs.dirs" file
+///   written by [xdg-user-dirs] on other Unix platforms. Only
+///   [`KnownFolder::Desktop`] has a default if it is not configured.
+///
+/// However, the implementation is subject to change. This section is only
+/// informative.
+///
+/// # Errors
+///
+/// Returns an error if the folder is not defined for the current user or
+/// platform.
+///
+/// # Examples
+///
+/// ```
+/// use normpath::KnownFolder;
+///
+/// if let Ok(path) = normpath::known_folder(KnownFolder::Home) {
+///     assert!(path.is_absolute());
+/// }
+/// ```
+///
+/// [`SHGetKnownFolderPath`]: https://learn.microsoft.com/en-us/windows/win32/api/shlobj_core/nf-shlobj_core-shgetknownfolderpath
+/// [XDG Base Directory Specification]: https://specifications.freedesktop.org/basedir-spec/latest/
+/// [xdg-user-dirs]: https://www.freedesktop.org/wiki/Software/xdg-user-dirs/
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+#[inline]
+pub fn known_folder(folder: KnownFolder) -> io::Result<BasePathBuf> {
+    known_folder_with(folder, &SystemEnv)
+}
+
+/// Equivalent to [`known_folder`] but reads environment variables from
+/// `env`.
+///
+/// The environment is not used on Windows.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use std::ffi::OsStr;
+/// use std::ffi::OsString;
+/// use std::path::Path;
+///
+/// use normpath::EnvProvider;
+/// use normpath::KnownFolder;
+///
+/// struct FakeEnv;
+///
+/// impl EnvProvider for FakeEnv {
+///     fn var(&self, name: &OsStr) -> Option<OsString> {
+///         (name == "HOME").then(|| "/home/foo".into())
+///     }
+/// }
+///
+/// if cfg!(target_os = "linux") {
+///     assert_eq!(
+///         Path::new("/home/foo/.cache"),
+///         normpath::known_folder_with(KnownFolder::Cache, &FakeEnv)?,
+///     );
+/// }
+/// #
+/// # Ok::<_, io::Error>(())
+/// ```
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+#[inline]
+pub fn known_folder_with<E>(
+    folder: KnownFolder,
+    env: &E,
+) -> io::Result<BasePathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    localize::known_folder(folder, env).and_then(BasePathBuf::new)
+}
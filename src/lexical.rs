@@ -0,0 +1,129 @@
+use std::ffi::OsStr;
+
+pub(crate) fn is_separator(byte: u8) -> bool {
+    [b'/', b'\\'].contains(&byte)
+}
+
+pub(crate) fn os_str(string: &[u8]) -> &OsStr {
+    // SAFETY: Strings are only split at ASCII characters.
+    unsafe { OsStr::from_encoded_bytes_unchecked(string) }
+}
+
+// Equivalent to [std::path::Prefix] but can be parsed on any platform.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Prefix<'a> {
+    Verbatim(&'a [u8]),
+    VerbatimUnc(&'a [u8], &'a [u8]),
+    VerbatimDisk(u8),
+    DeviceNs(&'a [u8]),
+    Unc(&'a [u8], &'a [u8]),
+    Disk(u8),
+}
+
+impl Prefix<'_> {
+    pub(crate) fn is_verbatim(self) -> bool {
+        matches!(
+            self,
+            Self::Verbatim(_) | Self::VerbatimUnc(..) | Self::VerbatimDisk(_),
+        )
+    }
+}
+
+fn split_component(path: &[u8], verbatim: bool) -> (&[u8], &[u8]) {
+    let end = path
+        .iter()
+        .position(|&x| x == b'\\' || (!verbatim && x == b'/'))
+        .unwrap_or(path.len());
+    path.split_at(end)
+}
+
+fn parse_drive(path: &[u8]) -> Option<u8> {
+    match path {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => Some(*drive),
+        _ => None,
+    }
+}
+
+// Parses a Windows prefix using the same rules as the standard library.
+// Returns the prefix and the rest of the path, which might start with a
+// separator.
+pub(crate) fn parse_prefix(path: &[u8]) -> Option<(Prefix<'_>, &[u8])> {
+    // The meaning of verbatim paths can change when they use a different
+    // separator.
+    if let Some(path) = path.strip_prefix(br"\\?\") {
+        if let Some(path) = path.strip_prefix(br"UNC\") {
+            let (server, path) = split_component(path, true);
+            let (share, path) =
+                split_component(path.get(1..).unwrap_or_default(), true);
+            return Some((Prefix::VerbatimUnc(server, share), path));
+        }
+        if let Some(drive) = parse_drive(path) {
+            if path.get(2).map_or(true, |&x| x == b'\\') {
+                return Some((Prefix::VerbatimDisk(drive), &path[2..]));
+            }
+        }
+        let (prefix, path) = split_component(path, true);
+        return Some((Prefix::Verbatim(prefix), path));
+    }
+
+    let path = match path {
+        [x, y, path @ ..] if is_separator(*x) && is_separator(*y) => path,
+        _ => return parse_drive(path).map(|x| (Prefix::Disk(x), &path[2..])),
+    };
+
+    if let [b'.', x, path @ ..] = path {
+        if is_separator(*x) {
+            let (prefix, path) = split_component(path, false);
+            return Some((Prefix::DeviceNs(prefix), path));
+        }
+    }
+
+    let (server, path) = split_component(path, false);
+    let (share, path) =
+        split_component(path.get(1..).unwrap_or_default(), false);
+    (!server.is_empty() && !share.is_empty())
+        .then_some((Prefix::Unc(server, share), path))
+}
+
+// Returns whether a file name refers to a legacy device when used as the last
+// component of a path that is not verbatim. Extensions and trailing spaces
+// are ignored when matching these names.
+pub(crate) fn is_reserved_name(name: &[u8]) -> bool {
+    const SUPERSCRIPT_DIGITS: [&[u8]; 3] =
+        ["¹".as_bytes(), "²".as_bytes(), "³".as_bytes()];
+
+    let end = name
+        .iter()
+        .position(|&x| x == b'.' || x == b':')
+        .unwrap_or(name.len());
+    let name = name[..end].trim_ascii_end();
+    if name.len() < 3 {
+        return false;
+    }
+    let (base, suffix) = name.split_at(3);
+    match &*base.to_ascii_uppercase() {
+        b"AUX" | b"NUL" | b"PRN" => suffix.is_empty(),
+        b"CON" => {
+            suffix.is_empty()
+                || suffix.eq_ignore_ascii_case(b"IN$")
+                || suffix.eq_ignore_ascii_case(b"OUT$")
+        }
+        b"COM" | b"LPT" => match suffix {
+            [x] => (b'1'..=b'9').contains(x),
+            _ => SUPERSCRIPT_DIGITS.contains(&suffix),
+        },
+        _ => false,
+    }
+}
+
+// Returns the number of UTF-16 code units needed to represent a string.
+pub(crate) fn utf16_len(string: &[u8]) -> usize {
+    string
+        .iter()
+        .map(|&x| match x {
+            0x80..=0xBF => 0,
+            0xF0..=0xFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
@@ -15,7 +15,27 @@
 //! - [`BasePathBuf::push`] (replaces [`PathBuf::push`])
 //!
 //! Additionally, these methods can be used for other enhancements:
-//! - [`PathExt::localize_name`]
+//! - [`BasePath::to_file_url`] and [`BasePathBuf::from_file_url`]
+//! - [`BasePathBuf::expand_shell`]
+//! - [`BasePath::extensions`] and [`BasePathBuf::replace_extensions`]
+//! - [`BasePath::find_ancestor_with`] and [`BasePath::ancestors_with`]
+//! - [`BasePath::to_wsl`] and [`BasePathBuf::from_wsl`] (and similar methods
+//!   for other environments)
+//! - [`BasePath::join_archive_entry`]
+//! - [`BasePath::join_rel`] and [`BasePath::strip_base`]
+//! - [`BasePath::join_sanitized`] and [`sanitize_file_name`]
+//! - [`BasePath::split_prefix`]
+//! - [`BasePath::to_verbatim`] and [`BasePath::simplified`]
+//! - [`BasePath::validate_for`]
+//! - [`known_folder`]
+//! - [`PathExt::localize_name`], [`PathExt::localize_name_in`],
+//!   [`PathExt::try_localize_name`], and [`BasePath::localize_path`]
+//! - [`BasePath::resolve_localized_child`]
+//! - [`PathExt::localize_type_name`]
+//! - [`PathExt::is_hidden`]
+//! - [`BasePath::mount_info`] (Linux only)
+//! - [`BasePath::same_file`] and [`BasePath::same_device`]
+//! - [`BasePathBuf::logical_current_dir`] and [`LogicalPathBuf`]
 //!
 //! # Features
 //!
@@ -25,7 +45,9 @@
 //! ### Optional Features
 //!
 //! - **localization** -
-//!   Provides [`PathExt::localize_name`] and [`BasePath::localize_name`].
+//!   Provides [`PathExt::localize_name`], [`PathExt::try_localize_name`],
+//!   [`BasePath::localize_name`], [`BasePath::localize_path`], and
+//!   [`known_folder`].
 //!
 //! - **print\_bytes** -
 //!   Provides implementations of [`print_bytes::ToBytes`] for [`BasePath`] and
@@ -46,9 +68,9 @@
 //! returned for the same platform. Whichever can be constructed most
 //! efficiently will be returned.
 //!
-//! All traits are [sealed], meaning that they can only be implemented by this
-//! crate. Otherwise, backward compatibility would be more difficult to
-//! maintain for new features.
+//! All traits except [`EnvProvider`] are [sealed], meaning that they can only
+//! be implemented by this crate. Otherwise, backward compatibility would be
+//! more difficult to maintain for new features.
 //!
 //! # Sponsorship
 //!
@@ -103,25 +125,98 @@
 use std::borrow::Cow;
 #[cfg(feature = "localization")]
 use std::ffi::OsStr;
-use std::io;
 #[cfg(feature = "localization")]
-use std::path::Component;
+use std::ffi::OsString;
+use std::io;
 use std::path::Path;
 
+#[cfg(feature = "localization")]
+use error::LocalizeError;
+
+mod ancestors;
+pub use ancestors::Ancestors;
+pub use ancestors::AncestorsWith;
+
+mod archive;
+
 mod base;
 pub use base::BasePath;
 pub use base::BasePathBuf;
 
 mod cmp;
 
+mod env;
+pub use env::EnvProvider;
+pub use env::SystemEnv;
+
 pub mod error;
 
+mod expand;
+pub use expand::ShellSyntax;
+
+mod extensions;
+pub use extensions::Extensions;
+
+mod file_id;
+pub use file_id::FileId;
+
 #[cfg_attr(windows, path = "windows/mod.rs")]
 #[cfg_attr(not(windows), path = "common/mod.rs")]
 mod imp;
 #[cfg(feature = "localization")]
 use imp::localize;
 
+#[cfg(feature = "localization")]
+mod known_folder;
+#[cfg(feature = "localization")]
+pub use known_folder::known_folder;
+#[cfg(feature = "localization")]
+pub use known_folder::known_folder_with;
+#[cfg(feature = "localization")]
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+pub use known_folder::KnownFolder;
+
+mod lexical;
+
+#[cfg(feature = "localization")]
+mod localized;
+#[cfg(feature = "localization")]
+pub use localized::Localized;
+#[cfg(feature = "localization")]
+pub use localized::LocalizedComponents;
+#[cfg(feature = "localization")]
+pub use localized::LocalizedNameCache;
+
+mod logical;
+pub use logical::LogicalPathBuf;
+
+#[cfg(target_os = "linux")]
+mod mount;
+#[cfg(target_os = "linux")]
+pub use mount::MountInfo;
+
+mod prefix;
+pub use prefix::PrefixInfo;
+
+mod rel;
+pub use rel::RelPath;
+pub use rel::RelPathBuf;
+
+mod sanitize;
+pub use sanitize::sanitize_file_name;
+pub use sanitize::SanitizeOptions;
+
+mod translate;
+pub use translate::PosixStyle;
+pub use translate::PosixTranslator;
+
+mod url;
+
+mod validate;
+pub use validate::Platform;
+
+mod verbatim;
+
 /// Additional methods added to [`Path`].
 pub trait PathExt: private::Sealed {
     /// Expands `self` from its short form, if the convention exists for the
@@ -167,10 +262,55 @@ pub trait PathExt: private::Sealed {
     where
         Self: ToOwned;
 
+    /// Returns true if the system file manager hides this file by default.
+    ///
+    /// The file itself is checked, so symbolic links are not followed. The
+    /// file must exist, even on platforms where its metadata is not needed.
+    /// If the path ends with a `..` component or has no name, false is
+    /// returned unless the file has a hidden attribute.
+    ///
+    /// # Implementation
+    ///
+    /// Currently, a file is considered hidden if:
+    ///
+    /// - its name starts with a `.` character on Unix platforms.
+    /// - it has the [`UF_HIDDEN`] flag on MacOS.
+    /// - its name is listed in a ".hidden" file in the same directory, as
+    ///   supported by GNOME and KDE, on other Unix platforms.
+    /// - it has the [`FILE_ATTRIBUTE_HIDDEN`] attribute on Windows.
+    ///
+    /// However, the implementation is subject to change. This section is only
+    /// informative.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata of the file cannot be read, or if a
+    /// ".hidden" file exists but cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io;
+    /// use std::path::Path;
+    ///
+    /// use normpath::PathExt;
+    ///
+    /// if cfg!(unix) {
+    ///     assert!(Path::new("/home/foo/.bashrc").is_hidden()?);
+    /// }
+    /// #
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    ///
+    /// [`FILE_ATTRIBUTE_HIDDEN`]: https://learn.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants
+    /// [`UF_HIDDEN`]: https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/chflags.2.html
+    fn is_hidden(&self) -> io::Result<bool>;
+
     /// Returns the localized simple name for this path.
     ///
     /// If the path does not exist or localization is not possible, the last
-    /// component will be returned.
+    /// component will be returned. To determine why localization did not
+    /// occur, use [`try_localize_name`] instead.
     ///
     /// The returned string should only be used for display to users. It will
     /// be as similar as possible to the name displayed by the system file
@@ -199,7 +339,15 @@ pub trait PathExt: private::Sealed {
     /// You should call this function from a background thread. Failure to do
     /// so could cause the UI to stop responding.
     ///
-    /// </blockquote></div></li></ul>
+    /// </blockquote></div></li><li>
+    ///
+    /// the "Name" entry of ".directory" files written by KDE, the message
+    /// catalog of [xdg-user-dirs] for its directories, or the ".localized"
+    /// bundles described by [`localize_name_in`], on other Unix platforms.
+    /// The locale is read from the `LC_ALL`, `LC_MESSAGES`, or `LANG`
+    /// environment variable.
+    ///
+    /// </li></ul>
     ///
     /// However, the implementation is subject to change. This section is only
     /// informative.
@@ -227,12 +375,171 @@ pub trait PathExt: private::Sealed {
     /// [displayNameAtPath]: https://developer.apple.com/documentation/foundation/nsfilemanager/1409751-displaynameatpath
     /// [normalized]: Self::normalize
     /// [rust-lang/rfcs#845]: https://github.com/rust-lang/rfcs/issues/845
+    /// [`localize_name_in`]: Self::localize_name_in
     /// [`SHGetFileInfoW`]: https://docs.microsoft.com/en-us/windows/win32/api/shellapi/nf-shellapi-shgetfileinfow
+    /// [`try_localize_name`]: Self::try_localize_name
+    /// [xdg-user-dirs]: https://www.freedesktop.org/wiki/Software/xdg-user-dirs/
     #[cfg(feature = "localization")]
     #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
     #[must_use]
     fn localize_name(&self) -> Cow<'_, OsStr>;
 
+    /// Equivalent to [`localize_name`] but localizes the name for `locale`,
+    /// instead of the language of the current user.
+    ///
+    /// The locale should be a POSIX locale name, such as "de_DE", or a
+    /// language tag, such as "de-DE". Less specific translations, such as
+    /// "de", are used when no translation exists for the locale. The "C" and
+    /// "POSIX" locales do not have translations.
+    ///
+    /// # Implementation
+    ///
+    /// The system APIs used by [`localize_name`] only support the language of
+    /// the current user, so this method reads translations directly:
+    ///
+    /// - On MacOS, a directory named "Foo.localized" can contain a file, such
+    ///   as ".localized/de.strings", with an entry for the name "Foo". These
+    ///   ".localized" bundles are also read on other Unix platforms, after
+    ///   the sources listed for [`localize_name`].
+    /// - On Windows, localization is not supported, so the last component is
+    ///   always returned.
+    ///
+    /// However, the implementation is subject to change. This section is only
+    /// informative.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`localize_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::PathExt;
+    ///
+    /// let path = Path::new("/foo/bar/test.rs");
+    /// assert_eq!("test.rs", &*path.localize_name_in("de_DE"));
+    /// ```
+    ///
+    /// [`localize_name`]: Self::localize_name
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[must_use]
+    fn localize_name_in(&self, locale: &str) -> Cow<'_, OsStr>;
+
+    /// Equivalent to [`localize_name_in`] but reads environment variables
+    /// from `env`.
+    ///
+    /// For more information, see [`localize_name_with`]. The locale
+    /// environment variables are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`localize_name`].
+    ///
+    /// [`localize_name`]: Self::localize_name
+    /// [`localize_name_in`]: Self::localize_name_in
+    /// [`localize_name_with`]: Self::localize_name_with
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[must_use]
+    fn localize_name_in_with<E>(
+        &self,
+        locale: &str,
+        env: &E,
+    ) -> Cow<'_, OsStr>
+    where
+        E: EnvProvider + ?Sized;
+
+    /// Equivalent to [`localize_name`] but reads environment variables from
+    /// `env`.
+    ///
+    /// The environment is only used on Unix platforms other than MacOS. It
+    /// determines the locale, the configuration directory containing
+    /// "user-dirs.dirs", and the data directories containing message
+    /// catalogs.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`localize_name`].
+    ///
+    /// [`localize_name`]: Self::localize_name
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[must_use]
+    fn localize_name_with<E>(&self, env: &E) -> Cow<'_, OsStr>
+    where
+        E: EnvProvider + ?Sized;
+
+    /// Returns a description of the type of this file, localized for the
+    /// current user.
+    ///
+    /// The description is the one displayed by the system file manager, such
+    /// as "Text Document" or "Folder". It should only be used for display to
+    /// users. If the type cannot be determined or does not have a description,
+    /// [`None`] is returned.
+    ///
+    /// # Implementation
+    ///
+    /// Currently, this method uses:
+    ///
+    /// <ul><li>
+    ///
+    /// [`SHGetFileInfoW`] on Windows, which has the same usage note as for
+    /// [`localize_name`].
+    ///
+    /// </li><li>
+    ///
+    /// the [shared-mime-info] database on Unix platforms other than MacOS.
+    /// The type is found by matching the file name against the globs in the
+    /// database, or by reading the start of the file if no glob matches. The
+    /// locale is read the same way as for [`localize_name`].
+    ///
+    /// </li></ul>
+    ///
+    /// Other platforms, including MacOS, are not supported, so [`None`] is
+    /// always returned.
+    ///
+    /// However, the implementation is subject to change. This section is only
+    /// informative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::PathExt;
+    ///
+    /// if let Some(type_name) = Path::new("Cargo.toml").localize_type_name() {
+    ///     println!("{}", type_name.display());
+    /// }
+    /// ```
+    ///
+    /// [`localize_name`]: Self::localize_name
+    /// [shared-mime-info]: https://specifications.freedesktop.org/shared-mime-info-spec/latest/
+    /// [`SHGetFileInfoW`]: https://docs.microsoft.com/en-us/windows/win32/api/shellapi/nf-shellapi-shgetfileinfow
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[must_use]
+    fn localize_type_name(&self) -> Option<OsString>;
+
+    /// Equivalent to [`localize_type_name`] but reads environment variables
+    /// from `env`.
+    ///
+    /// The environment is only used on Unix platforms other than MacOS. It
+    /// determines the locale and the data directories containing the
+    /// shared-mime-info database, which are read from `XDG_DATA_HOME` and
+    /// `XDG_DATA_DIRS`.
+    ///
+    /// [`localize_type_name`]: Self::localize_type_name
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    #[must_use]
+    fn localize_type_name_with<E>(&self, env: &E) -> Option<OsString>
+    where
+        E: EnvProvider + ?Sized;
+
     /// Normalizes `self` relative to the current directory.
     ///
     /// The purpose of normalization is to remove `.` and `..` components of a
@@ -380,6 +687,55 @@ pub trait PathExt: private::Sealed {
     fn shorten(&self) -> io::Result<Cow<'_, Self>>
     where
         Self: ToOwned;
+
+    /// Equivalent to [`localize_name`] but returns an error instead of
+    /// panicking or silently falling back to the last component.
+    ///
+    /// If the platform's localization API has no localized name for the path,
+    /// the last component is returned, and [`Localized::is_localized`] returns
+    /// false.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is empty, if it ends with a `..`
+    /// component, or if the platform's localization API cannot be used for the
+    /// path. See [`LocalizeError`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::error::LocalizeError;
+    /// use normpath::PathExt;
+    ///
+    /// assert_eq!(
+    ///     Err(LocalizeError::ParentComponent),
+    ///     Path::new("/foo/..").try_localize_name(),
+    /// );
+    /// ```
+    ///
+    /// [`localize_name`]: Self::localize_name
+    /// [`LocalizeError`]: error::LocalizeError
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    fn try_localize_name(&self) -> Result<Localized<'_>, LocalizeError>;
+
+    /// Equivalent to [`try_localize_name`] but reads environment variables
+    /// from `env`.
+    ///
+    /// For more information, see [`localize_name_with`].
+    ///
+    /// [`localize_name_with`]: Self::localize_name_with
+    /// [`try_localize_name`]: Self::try_localize_name
+    #[cfg(feature = "localization")]
+    #[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+    fn try_localize_name_with<E>(
+        &self,
+        env: &E,
+    ) -> Result<Localized<'_>, LocalizeError>
+    where
+        E: EnvProvider + ?Sized;
 }
 
 impl PathExt for Path {
@@ -388,22 +744,57 @@ impl PathExt for Path {
         imp::expand(self)
     }
 
+    #[inline]
+    fn is_hidden(&self) -> io::Result<bool> {
+        imp::is_hidden(self)
+    }
+
     #[cfg(feature = "localization")]
     #[inline]
     fn localize_name(&self) -> Cow<'_, OsStr> {
-        let Some(name) = self.components().next_back() else {
-            return Cow::Borrowed(OsStr::new(""));
-        };
-        assert_ne!(
-            Component::ParentDir,
-            name,
-            "path ends with a `..` component: \"{}\"",
-            self.display(),
-        );
-
-        localize::name(self)
-            .map(Cow::Owned)
-            .unwrap_or_else(|| Cow::Borrowed(name.as_os_str()))
+        self.localize_name_with(&SystemEnv)
+    }
+
+    #[cfg(feature = "localization")]
+    #[inline]
+    fn localize_name_in(&self, locale: &str) -> Cow<'_, OsStr> {
+        self.localize_name_in_with(locale, &SystemEnv)
+    }
+
+    #[cfg(feature = "localization")]
+    #[inline]
+    fn localize_name_in_with<E>(&self, locale: &str, env: &E) -> Cow<'_, OsStr>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        localized::localize_name(
+            self,
+            &localize::Localizer::with_locale(locale, env),
+        )
+    }
+
+    #[cfg(feature = "localization")]
+    #[inline]
+    fn localize_name_with<E>(&self, env: &E) -> Cow<'_, OsStr>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        localized::localize_name(self, &localize::Localizer::new(env))
+    }
+
+    #[cfg(feature = "localization")]
+    #[inline]
+    fn localize_type_name(&self) -> Option<OsString> {
+        self.localize_type_name_with(&SystemEnv)
+    }
+
+    #[cfg(feature = "localization")]
+    #[inline]
+    fn localize_type_name_with<E>(&self, env: &E) -> Option<OsString>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        localize::Localizer::new(env).type_name(self)
     }
 
     #[inline]
@@ -421,6 +812,23 @@ impl PathExt for Path {
     fn shorten(&self) -> io::Result<Cow<'_, Self>> {
         imp::shorten(self)
     }
+
+    #[cfg(feature = "localization")]
+    #[inline]
+    fn try_localize_name(&self) -> Result<Localized<'_>, LocalizeError> {
+        self.try_localize_name_with(&SystemEnv)
+    }
+
+    #[cfg(feature = "localization")]
+    fn try_localize_name_with<E>(
+        &self,
+        env: &E,
+    ) -> Result<Localized<'_>, LocalizeError>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        localized::try_localize_name(self, &localize::Localizer::new(env))
+    }
 }
 
 mod private {
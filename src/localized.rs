@@ -0,0 +1,346 @@
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io;
+use std::iter::FusedIterator;
+use std::path::Component;
+use std::path::Components;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::MAIN_SEPARATOR_STR;
+
+use super::error::LocalizeError;
+use super::localize::Localizer;
+use super::BasePath;
+use super::BasePathBuf;
+use super::EnvProvider;
+use super::SystemEnv;
+
+/// A name returned by [`PathExt::try_localize_name`].
+///
+/// [`PathExt::try_localize_name`]: super::PathExt::try_localize_name
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Localized<'a> {
+    pub(super) name: Cow<'a, OsStr>,
+    pub(super) localized: bool,
+}
+
+impl<'a> Localized<'a> {
+    /// Returns the name, which is the last component of the path if
+    /// [`is_localized`] returns false.
+    ///
+    /// [`is_localized`]: Self::is_localized
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Equivalent to [`name`] but takes ownership of `self`.
+    ///
+    /// [`name`]: Self::name
+    #[inline]
+    #[must_use]
+    pub fn into_name(self) -> Cow<'a, OsStr> {
+        self.name
+    }
+
+    /// Returns true if the name was provided by the platform's localization
+    /// API.
+    ///
+    /// When no localized name exists, the last component of the path is used
+    /// as a fallback, and this method returns false.
+    #[inline]
+    #[must_use]
+    pub fn is_localized(&self) -> bool {
+        self.localized
+    }
+}
+
+/// An iterator over the components of a [`BasePath`] and their localized
+/// names.
+///
+/// This struct is created by [`BasePath::localize_components`]. See its
+/// documentation for more information.
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct LocalizedComponents<'a> {
+    components: Components<'a>,
+    path: PathBuf,
+    localizer: Localizer,
+}
+
+impl<'a> LocalizedComponents<'a> {
+    pub(super) fn new<E>(path: &'a BasePath, env: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        Self {
+            components: path.components(),
+            path: PathBuf::new(),
+            localizer: Localizer::new(env),
+        }
+    }
+}
+
+impl Debug for LocalizedComponents<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalizedComponents")
+            .field("components", &self.components)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FusedIterator for LocalizedComponents<'_> {}
+
+impl<'a> Iterator for LocalizedComponents<'a> {
+    type Item = (Component<'a>, Cow<'a, OsStr>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let component = self.components.next()?;
+        self.path.push(component);
+
+        let name = if let Component::Normal(name) = component {
+            self.localizer
+                .name(&self.path)
+                .ok()
+                .flatten()
+                .map_or(Cow::Borrowed(name), Cow::Owned)
+        } else {
+            Cow::Borrowed(component.as_os_str())
+        };
+        Some((component, name))
+    }
+}
+
+// Names are compared like the default file system of the platform.
+fn names_match(name: &OsStr, other: &OsStr, ignore_case: bool) -> bool {
+    if name == other {
+        return true;
+    }
+    if !ignore_case {
+        return false;
+    }
+    let (Some(name), Some(other)) = (name.to_str(), other.to_str()) else {
+        return false;
+    };
+    name.chars()
+        .flat_map(char::to_lowercase)
+        .eq(other.chars().flat_map(char::to_lowercase))
+}
+
+fn children(
+    parent: &BasePath,
+    localizer: &Localizer,
+) -> io::Result<Vec<(OsString, BasePathBuf)>> {
+    parent
+        .read_dir()?
+        .map(|entry| {
+            let name = entry?.file_name();
+            let path = parent.join(&name);
+            let name = localizer
+                .name(path.as_path())
+                .ok()
+                .flatten()
+                .unwrap_or(name);
+            Ok((name, path))
+        })
+        .collect()
+}
+
+// Exact matches are preferred to those that only match when ignoring case.
+fn find_child(
+    children: &[(OsString, BasePathBuf)],
+    display_name: &OsStr,
+) -> Option<BasePathBuf> {
+    let ignore_case =
+        cfg!(any(target_os = "ios", target_os = "macos", windows));
+    children
+        .iter()
+        .find(|(name, _)| name == display_name)
+        .or_else(|| {
+            children
+                .iter()
+                .find(|(name, _)| names_match(name, display_name, ignore_case))
+        })
+        .map(|(_, path)| path.clone())
+}
+
+pub(super) fn resolve_localized_child(
+    parent: &BasePath,
+    display_name: &OsStr,
+    localizer: &Localizer,
+) -> io::Result<Option<BasePathBuf>> {
+    children(parent, localizer).map(|x| find_child(&x, display_name))
+}
+
+/// A cache of localized names, used to find the children of directories
+/// from the names displayed to users.
+///
+/// The children of each directory are only read and localized the first
+/// time that the directory is searched. Afterward, the cache must be
+/// [cleared] for changes to the directory to be found.
+///
+/// For more information, see [`BasePath::resolve_localized_child`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use std::path::Path;
+///
+/// use normpath::BasePath;
+/// use normpath::LocalizedNameCache;
+///
+/// let mut cache = LocalizedNameCache::new();
+/// let home = BasePath::new(Path::new("/home/foo"))?;
+/// for name in ["Dokumente", "Bilder"] {
+///     if let Some(path) = cache.resolve(&home, name)? {
+///         println!("{}", path.as_path().display());
+///     }
+/// }
+/// #
+/// # Ok::<_, io::Error>(())
+/// ```
+///
+/// [cleared]: Self::clear
+#[cfg_attr(normpath_docs_rs, doc(cfg(feature = "localization")))]
+pub struct LocalizedNameCache {
+    children: HashMap<BasePathBuf, Vec<(OsString, BasePathBuf)>>,
+    localizer: Localizer,
+}
+
+impl LocalizedNameCache {
+    /// Creates an empty cache.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_env(&SystemEnv)
+    }
+
+    /// Equivalent to [`new`] but reads environment variables from `env`.
+    ///
+    /// For more information, see [`PathExt::localize_name_with`].
+    ///
+    /// [`new`]: Self::new
+    /// [`PathExt::localize_name_with`]: super::PathExt::localize_name_with
+    #[inline]
+    #[must_use]
+    pub fn with_env<E>(env: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        Self {
+            children: HashMap::new(),
+            localizer: Localizer::new(env),
+        }
+    }
+
+    /// Removes all directories from the cache.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.children.clear();
+    }
+
+    /// Equivalent to [`BasePath::resolve_localized_child`] but uses this
+    /// cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the children of `parent` are not cached and
+    /// cannot be read. Errors are not cached.
+    pub fn resolve<S>(
+        &mut self,
+        parent: &BasePath,
+        display_name: S,
+    ) -> io::Result<Option<BasePathBuf>>
+    where
+        S: AsRef<OsStr>,
+    {
+        let children = match self.children.entry(parent.to_owned()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(children(parent, &self.localizer)?)
+            }
+        };
+        Ok(find_child(children, display_name.as_ref()))
+    }
+}
+
+impl Debug for LocalizedNameCache {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalizedNameCache")
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for LocalizedNameCache {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(super) fn try_localize_name<'a>(
+    path: &'a Path,
+    localizer: &Localizer,
+) -> Result<Localized<'a>, LocalizeError> {
+    let name = path.components().next_back().ok_or(LocalizeError::Empty)?;
+    if name == Component::ParentDir {
+        return Err(LocalizeError::ParentComponent);
+    }
+
+    localizer.name(path).map(|x| match x {
+        Some(x) => Localized {
+            name: Cow::Owned(x),
+            localized: true,
+        },
+        None => Localized {
+            name: Cow::Borrowed(name.as_os_str()),
+            localized: false,
+        },
+    })
+}
+
+pub(super) fn localize_name<'a>(
+    path: &'a Path,
+    localizer: &Localizer,
+) -> Cow<'a, OsStr> {
+    match try_localize_name(path, localizer) {
+        Ok(name) => name.into_name(),
+        Err(LocalizeError::Empty) => Cow::Borrowed(OsStr::new("")),
+        Err(LocalizeError::ParentComponent) => {
+            panic!("path ends with a `..` component: \"{}\"", path.display(),)
+        }
+        Err(_) => Cow::Borrowed(
+            path.components()
+                .next_back()
+                .expect("path is empty")
+                .as_os_str(),
+        ),
+    }
+}
+
+pub(super) fn localize_path(components: LocalizedComponents<'_>) -> OsString {
+    let mut result = OsString::new();
+    let mut needs_separator = false;
+    for (component, name) in components {
+        let is_name =
+            !matches!(component, Component::Prefix(_) | Component::RootDir);
+        if needs_separator && is_name {
+            result.push(MAIN_SEPARATOR_STR);
+        }
+        result.push(name);
+        needs_separator = is_name;
+    }
+    result
+}
@@ -0,0 +1,257 @@
+use std::env;
+use std::ffi::OsStr;
+use std::io;
+use std::ops::Deref;
+use std::path::Component;
+use std::path::Components;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::imp;
+use super::BasePath;
+use super::BasePathBuf;
+use super::EnvProvider;
+use super::SystemEnv;
+
+// Shells only update the variable when changing directories, so it must be
+// compared to the physical directory before being trusted.
+fn pwd<E>(env: &E, current_dir: &Path) -> Option<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    let pwd = PathBuf::from(env.var(OsStr::new("PWD"))?);
+    if !pwd.is_absolute()
+        || pwd.components().any(|x| x == Component::ParentDir)
+    {
+        return None;
+    }
+    // Collecting the components removes `.` components and redundant
+    // separators.
+    let pwd: PathBuf = pwd.components().collect();
+    let id = imp::file_id(&pwd).ok()?;
+    (imp::file_id(current_dir).ok()? == id).then_some(pwd)
+}
+
+pub(super) fn current_dir<E>(env: &E) -> io::Result<BasePathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    let current_dir = env::current_dir()?;
+    BasePathBuf::new(pwd(env, &current_dir).unwrap_or(current_dir))
+}
+
+// Splits the prefix and root from the remaining components.
+fn split_root(path: &Path) -> (PathBuf, Components<'_>) {
+    let mut components = path.components();
+    let mut root = PathBuf::new();
+    while let Some(component @ (Component::Prefix(_) | Component::RootDir)) =
+        components.clone().next()
+    {
+        root.push(component);
+        let _ = components.next();
+    }
+    (root, components)
+}
+
+/// A [`BasePathBuf`] that resolves `..` components lexically, like a shell
+/// using `cd -L`.
+///
+/// Pushing `..` removes the last component of the path, even if it was a
+/// symbolic link. Therefore, the path is always displayed as the user
+/// navigated to it. The path never contains `.` components, and it only
+/// contains `..` components if it is relative and has no parent, such as
+/// `X:..` on Windows.
+///
+/// Dereferencing returns the wrapped [`BasePath`], so methods such as
+/// [`BasePath::normalize`] still access the file system and resolve
+/// symbolic links physically.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use normpath::BasePathBuf;
+/// use normpath::LogicalPathBuf;
+///
+/// if cfg!(windows) {
+///     let path = BasePathBuf::try_new(r"X:\foo\link").unwrap();
+///     let mut path = LogicalPathBuf::new(path);
+///     path.push(r"..\bar");
+///     assert_eq!(Path::new(r"X:\foo\bar"), path.as_path());
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LogicalPathBuf(BasePathBuf);
+
+impl LogicalPathBuf {
+    /// Creates a new logical path, resolving `.` and `..` components in
+    /// `path` lexically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    /// use normpath::LogicalPathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = BasePathBuf::try_new(r"X:\foo\.\..\bar").unwrap();
+    ///     let path = LogicalPathBuf::new(path);
+    ///     assert_eq!(Path::new(r"X:\bar"), path.as_path());
+    /// }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(path: BasePathBuf) -> Self {
+        let (root, components) = split_root(path.as_path());
+        let mut result = Self(BasePathBuf(root));
+        result.push_components(components);
+        result
+    }
+
+    /// Equivalent to [`BasePathBuf::logical_current_dir`] but returns a
+    /// logical path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the physical working directory cannot be read.
+    #[inline]
+    pub fn current_dir() -> io::Result<Self> {
+        Self::current_dir_with(&SystemEnv)
+    }
+
+    /// Equivalent to [`current_dir`] but reads environment variables from
+    /// `env`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the physical working directory cannot be read.
+    ///
+    /// [`current_dir`]: Self::current_dir
+    #[inline]
+    pub fn current_dir_with<E>(env: &E) -> io::Result<Self>
+    where
+        E: EnvProvider + ?Sized,
+    {
+        current_dir(env).map(Self::new)
+    }
+
+    /// Returns the wrapped path as a base path.
+    #[inline]
+    #[must_use]
+    pub fn as_base_path(&self) -> &BasePath {
+        &self.0
+    }
+
+    /// Returns the wrapped base path.
+    #[inline]
+    #[must_use]
+    pub fn into_base_path_buf(self) -> BasePathBuf {
+        self.0
+    }
+
+    fn pop_lexically(&mut self) {
+        match self.0.components().next_back() {
+            Some(Component::Normal(_)) => {
+                let _ = self.0.pop_unchecked();
+            }
+            // The parent of a root is itself. However, a prefix without a
+            // root is relative to the current directory of the drive.
+            Some(Component::RootDir) => {}
+            _ => self.0 .0.push(".."),
+        }
+    }
+
+    fn push_components(&mut self, components: Components<'_>) {
+        for component in components {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => self.pop_lexically(),
+                Component::Normal(name) => self.0 .0.push(name),
+                Component::Prefix(_) | Component::RootDir => {
+                    unreachable!("root component after other components");
+                }
+            }
+        }
+    }
+
+    /// Equivalent to [`BasePathBuf::push`] but resolves `.` and `..`
+    /// components in `path` lexically.
+    ///
+    /// If `path` has a [prefix] or a root, it replaces `self` as it would for
+    /// [`BasePathBuf::push`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::BasePathBuf;
+    /// use normpath::LogicalPathBuf;
+    ///
+    /// if cfg!(windows) {
+    ///     let path = BasePathBuf::try_new(r"X:\foo").unwrap();
+    ///     let mut path = LogicalPathBuf::new(path);
+    ///     path.push(r"..\..\bar");
+    ///     assert_eq!(Path::new(r"X:\bar"), path.as_path());
+    /// }
+    /// ```
+    ///
+    /// [prefix]: ::std::path::Prefix
+    pub fn push<P>(&mut self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        let (root, components) = split_root(path.as_ref());
+        if !root.as_os_str().is_empty() {
+            self.0.push(root);
+        }
+        self.push_components(components);
+    }
+}
+
+impl AsRef<OsStr> for LogicalPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_ref()
+    }
+}
+
+impl AsRef<Path> for LogicalPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.0.as_ref()
+    }
+}
+
+impl AsRef<BasePath> for LogicalPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &BasePath {
+        &self.0
+    }
+}
+
+impl Deref for LogicalPathBuf {
+    type Target = BasePath;
+
+    #[inline]
+    fn deref(&self) -> &BasePath {
+        &self.0
+    }
+}
+
+impl From<BasePathBuf> for LogicalPathBuf {
+    #[inline]
+    fn from(value: BasePathBuf) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<LogicalPathBuf> for BasePathBuf {
+    #[inline]
+    fn from(value: LogicalPathBuf) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,224 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str;
+
+use super::BasePath;
+use super::BasePathBuf;
+
+pub(super) const MOUNTINFO: &str = "/proc/self/mountinfo";
+
+fn invalid_data(line: &[u8]) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "invalid line in mountinfo file: \"{}\"",
+            line.escape_ascii(),
+        ),
+    )
+}
+
+// Spaces, tabs, newlines, and backslashes are escaped as octal sequences,
+// such as `\040`.
+fn unescape(field: &[u8]) -> Option<Vec<u8>> {
+    let mut result = Vec::with_capacity(field.len());
+    let mut bytes = field.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte != b'\\' {
+            result.push(byte);
+            continue;
+        }
+        let digits = [*bytes.next()?, *bytes.next()?, *bytes.next()?];
+        let digits = str::from_utf8(&digits).ok()?;
+        result.push(u8::from_str_radix(digits, 8).ok()?);
+    }
+    Some(result)
+}
+
+fn parse_number<T>(field: &[u8]) -> Option<T>
+where
+    T: str::FromStr,
+{
+    str::from_utf8(field).ok()?.parse().ok()
+}
+
+fn parse_options(field: &[u8]) -> Option<Vec<String>> {
+    let field = String::from_utf8(unescape(field)?).ok()?;
+    Some(field.split(',').map(str::to_owned).collect())
+}
+
+/// Information about a mounted file system on Linux.
+///
+/// This struct is created by [`BasePath::mount_info`]. See its documentation
+/// for more information.
+///
+/// The values are read from a line of "/proc/self/mountinfo". For more
+/// information about each value, see the [proc_pid_mountinfo] man page.
+///
+/// [proc_pid_mountinfo]: https://man7.org/linux/man-pages/man5/proc_pid_mountinfo.5.html
+#[cfg_attr(normpath_docs_rs, doc(cfg(target_os = "linux")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MountInfo {
+    id: u32,
+    parent_id: u32,
+    device: (u32, u32),
+    root: PathBuf,
+    mount_point: BasePathBuf,
+    options: Vec<String>,
+    fs_type: String,
+    source: OsString,
+    super_options: Vec<String>,
+}
+
+impl MountInfo {
+    fn parse(line: &[u8]) -> Option<Self> {
+        let mut fields = line.split(|&x| x == b' ');
+        let id = parse_number(fields.next()?)?;
+        let parent_id = parse_number(fields.next()?)?;
+        let (major, minor) =
+            str::from_utf8(fields.next()?).ok()?.split_once(':')?;
+        let device = (major.parse().ok()?, minor.parse().ok()?);
+        let root = OsStr::from_bytes(&unescape(fields.next()?)?).into();
+        let mount_point =
+            BasePathBuf::new(OsStr::from_bytes(&unescape(fields.next()?)?))
+                .ok()?;
+        let options = parse_options(fields.next()?)?;
+
+        // Optional fields are terminated by a separator.
+        if !fields.any(|x| x == b"-") {
+            return None;
+        }
+        let fs_type = String::from_utf8(unescape(fields.next()?)?).ok()?;
+        let source = OsStr::from_bytes(&unescape(fields.next()?)?).into();
+        let super_options = parse_options(fields.next()?)?;
+
+        Some(Self {
+            id,
+            parent_id,
+            device,
+            root,
+            mount_point,
+            options,
+            fs_type,
+            source,
+            super_options,
+        })
+    }
+
+    /// Returns the unique ID of the mount, which can be used to group paths
+    /// by mount.
+    ///
+    /// IDs can be reused after a file system is unmounted.
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the ID of the parent mount, or the ID of this mount if it is
+    /// at the root of the mount tree.
+    #[inline]
+    #[must_use]
+    pub fn parent_id(&self) -> u32 {
+        self.parent_id
+    }
+
+    /// Returns the major and minor numbers of the device containing the file
+    /// system.
+    #[inline]
+    #[must_use]
+    pub fn device(&self) -> (u32, u32) {
+        self.device
+    }
+
+    /// Returns the directory in the file system that is mounted, which is
+    /// usually "/" unless it is a bind mount.
+    #[inline]
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the directory where the file system is mounted.
+    #[inline]
+    #[must_use]
+    pub fn mount_point(&self) -> &BasePath {
+        &self.mount_point
+    }
+
+    /// Returns the options for this mount, such as "rw" or "noatime".
+    #[inline]
+    #[must_use]
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Returns the type of the file system, such as "ext4", "nfs4", or
+    /// "fuse.sshfs".
+    #[inline]
+    #[must_use]
+    pub fn fs_type(&self) -> &str {
+        &self.fs_type
+    }
+
+    /// Returns the source of the file system, such as "/dev/sda1" or
+    /// "server:/export".
+    ///
+    /// Sources are specific to each type of file system, so this value is
+    /// not always a path.
+    #[inline]
+    #[must_use]
+    pub fn source(&self) -> &OsStr {
+        &self.source
+    }
+
+    /// Returns the options for the file system, which are shared by all of
+    /// its mounts.
+    #[inline]
+    #[must_use]
+    pub fn super_options(&self) -> &[String] {
+        &self.super_options
+    }
+}
+
+// Finds the mount with the longest mount point containing the path. Later
+// mounts are preferred, since they hide earlier mounts at the same point.
+pub(super) fn mount_info(
+    path: &BasePath,
+    mountinfo: &Path,
+) -> io::Result<MountInfo> {
+    let path = path.normalize()?;
+    let contents = fs::read(mountinfo)?;
+
+    let mut result: Option<MountInfo> = None;
+    for line in contents.split(|&x| x == b'\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mount =
+            MountInfo::parse(line).ok_or_else(|| invalid_data(line))?;
+        if !path.starts_with(&mount.mount_point) {
+            continue;
+        }
+        let len = mount.mount_point.components().count();
+        if result
+            .as_ref()
+            .map_or(true, |x| len >= x.mount_point.components().count())
+        {
+            result = Some(mount);
+        }
+    }
+    result.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "mount point not found: \"{}\"",
+                path.as_path().display(),
+            ),
+        )
+    })
+}
@@ -0,0 +1,90 @@
+use std::ffi::OsStr;
+use std::path::Component;
+use std::path::Path;
+use std::path::Prefix;
+
+use super::BasePath;
+
+/// The first part of an absolute path, which determines the volume, share,
+/// or device that the path refers to.
+///
+/// This enum is a platform-independent version of [`Prefix`], which is only
+/// constructed on Windows. It is returned by [`BasePath::prefix_info`] and
+/// [`BasePath::split_prefix`].
+///
+/// Values are compared literally, like [`Prefix`]. For example, the drive
+/// letters for `C:` and `c:` are not equal, even though they refer to the
+/// same drive.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum PrefixInfo<'a> {
+    /// A verbatim prefix, such as `\\?\cat_pics`.
+    Verbatim(&'a OsStr),
+
+    /// A verbatim prefix using UNC syntax, such as
+    /// `\\?\UNC\server\share`. The server and share are contained in that
+    /// order.
+    VerbatimUnc(&'a OsStr, &'a OsStr),
+
+    /// A verbatim prefix for a drive, such as `\\?\C:`.
+    VerbatimDisk(u8),
+
+    /// A prefix for a device namespace, such as `\\.\COM1`.
+    DeviceNs(&'a OsStr),
+
+    /// A prefix using UNC syntax, such as `\\server\share`. The server and
+    /// share are contained in that order.
+    Unc(&'a OsStr, &'a OsStr),
+
+    /// A prefix for a drive, such as `C:`.
+    Disk(u8),
+
+    /// The root directory on platforms other than Windows, which is written
+    /// as `/`.
+    PosixRoot,
+}
+
+impl PrefixInfo<'_> {
+    /// Equivalent to [`Prefix::is_verbatim`].
+    ///
+    /// Returns [`false`] for [`PrefixInfo::PosixRoot`].
+    #[inline]
+    #[must_use]
+    pub fn is_verbatim(self) -> bool {
+        matches!(
+            self,
+            Self::Verbatim(_) | Self::VerbatimUnc(..) | Self::VerbatimDisk(_),
+        )
+    }
+}
+
+impl<'a> From<Prefix<'a>> for PrefixInfo<'a> {
+    #[inline]
+    fn from(value: Prefix<'a>) -> Self {
+        match value {
+            Prefix::Verbatim(x) => Self::Verbatim(x),
+            Prefix::VerbatimUNC(x, y) => Self::VerbatimUnc(x, y),
+            Prefix::VerbatimDisk(x) => Self::VerbatimDisk(x),
+            Prefix::DeviceNS(x) => Self::DeviceNs(x),
+            Prefix::UNC(x, y) => Self::Unc(x, y),
+            Prefix::Disk(x) => Self::Disk(x),
+        }
+    }
+}
+
+pub(super) fn split_prefix(
+    path: &BasePath,
+) -> Option<(PrefixInfo<'_>, &Path)> {
+    let mut components = path.components();
+    let prefix = match components.next()? {
+        Component::Prefix(prefix) => prefix.kind().into(),
+        Component::RootDir => PrefixInfo::PosixRoot,
+        _ => return None,
+    };
+
+    let mut next_components = components.clone();
+    if next_components.next() == Some(Component::RootDir) {
+        components = next_components;
+    }
+    Some((prefix, components.as_path()))
+}
@@ -0,0 +1,371 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::mem;
+use std::ops::Deref;
+use std::path::Component;
+use std::path::Components;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::error::InvalidRelPathBufError;
+use super::error::InvalidRelPathError;
+
+fn is_rel(path: &Path) -> bool {
+    path.components()
+        .all(|x| matches!(x, Component::CurDir | Component::Normal(_)))
+}
+
+/// A borrowed path that can be joined to a [`BasePath`] without leaving it.
+///
+/// These paths have no [prefix], no root, and no `..` components. Therefore,
+/// joining one to another path using [`BasePath::join_rel`] always results in
+/// a descendant of that path, or the path itself if this path is empty.
+///
+/// Comparison traits compare paths literally, like [`BasePath`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use normpath::BasePath;
+/// use normpath::RelPath;
+///
+/// let base = if cfg!(windows) { r"X:\foo" } else { "/foo" };
+/// let base = BasePath::try_new(base).unwrap();
+///
+/// let path = RelPath::try_new("bar/baz").unwrap();
+/// assert_eq!(Path::new(base).join("bar/baz"), base.join_rel(path));
+///
+/// assert!(RelPath::try_new("../bar").is_err());
+/// assert!(RelPath::try_new("/bar").is_err());
+/// ```
+///
+/// [`BasePath`]: super::BasePath
+/// [`BasePath::join_rel`]: super::BasePath::join_rel
+/// [prefix]: ::std::path::Prefix
+#[derive(Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct RelPath(OsStr);
+
+impl RelPath {
+    fn from_inner(path: &OsStr) -> &Self {
+        // SAFETY: This struct has a layout that makes this operation safe.
+        unsafe { mem::transmute(path) }
+    }
+
+    /// Creates a new relative path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` has a [prefix], a root, or a `..`
+    /// component.
+    ///
+    /// [prefix]: ::std::path::Prefix
+    #[inline]
+    pub fn try_new<P>(path: &P) -> Result<&Self, InvalidRelPathError>
+    where
+        P: AsRef<Path> + ?Sized,
+    {
+        let path = path.as_ref();
+        if is_rel(path) {
+            Ok(Self::from_inner(path.as_os_str()))
+        } else {
+            Err(InvalidRelPathError(()))
+        }
+    }
+
+    /// Returns a reference to the wrapped path as a platform string.
+    #[inline]
+    #[must_use]
+    pub fn as_os_str(&self) -> &OsStr {
+        &self.0
+    }
+
+    /// Returns a reference to the wrapped path.
+    #[inline]
+    #[must_use]
+    pub fn as_path(&self) -> &Path {
+        Path::new(&self.0)
+    }
+
+    /// Equivalent to [`Path::components`].
+    #[inline]
+    pub fn components(&self) -> Components<'_> {
+        self.as_path().components()
+    }
+
+    /// Equivalent to [`Path::file_name`].
+    #[inline]
+    #[must_use]
+    pub fn file_name(&self) -> Option<&OsStr> {
+        self.as_path().file_name()
+    }
+
+    /// Returns [`true`] if this path is empty, which means that joining it
+    /// to another path would not change that path.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Equivalent to [`Path::join`].
+    ///
+    /// Joining two relative paths always results in a relative path.
+    #[inline]
+    #[must_use]
+    pub fn join(&self, path: &Self) -> RelPathBuf {
+        let mut base = self.to_owned();
+        base.push(path);
+        base
+    }
+
+    /// Equivalent to [`Path::parent`].
+    ///
+    /// Since these paths have no `..` components, the parent is never below
+    /// this path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::RelPath;
+    ///
+    /// let path = RelPath::try_new("foo/bar").unwrap();
+    /// let parent = path.parent().unwrap();
+    /// assert_eq!(Path::new("foo"), parent);
+    /// assert_eq!(Path::new(""), parent.parent().unwrap());
+    /// assert_eq!(None, parent.parent().unwrap().parent());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn parent(&self) -> Option<&Self> {
+        self.as_path()
+            .parent()
+            .map(|x| Self::from_inner(x.as_os_str()))
+    }
+}
+
+impl AsRef<OsStr> for RelPath {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for RelPath {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl AsRef<Self> for RelPath {
+    #[inline]
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl PartialEq<Path> for RelPath {
+    #[inline]
+    fn eq(&self, other: &Path) -> bool {
+        &self.0 == other.as_os_str()
+    }
+}
+
+impl PartialEq<RelPath> for Path {
+    #[inline]
+    fn eq(&self, other: &RelPath) -> bool {
+        other == self
+    }
+}
+
+impl ToOwned for RelPath {
+    type Owned = RelPathBuf;
+
+    #[inline]
+    fn to_owned(&self) -> Self::Owned {
+        RelPathBuf(self.0.to_owned().into())
+    }
+}
+
+/// An owned path that can be joined to a [`BasePath`] without leaving it.
+///
+/// For more information, see [`RelPath`].
+///
+/// [`BasePath`]: super::BasePath
+#[derive(Clone, Debug, Default)]
+pub struct RelPathBuf(PathBuf);
+
+impl RelPathBuf {
+    /// Creates an empty relative path.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Equivalent to [`RelPath::try_new`] but returns an owned path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::RelPathBuf;
+    ///
+    /// let path = RelPathBuf::try_new("foo/./bar").unwrap();
+    /// assert_eq!(Path::new("foo/./bar"), path.as_path());
+    ///
+    /// assert!(RelPathBuf::try_new("foo/../bar").is_err());
+    /// ```
+    #[inline]
+    pub fn try_new<P>(path: P) -> Result<Self, InvalidRelPathBufError>
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        if is_rel(&path) {
+            Ok(Self(path))
+        } else {
+            Err(InvalidRelPathBufError(path))
+        }
+    }
+
+    /// Returns the wrapped path as a platform string.
+    #[inline]
+    #[must_use]
+    pub fn into_os_string(self) -> OsString {
+        self.0.into_os_string()
+    }
+
+    /// Returns the wrapped path.
+    #[inline]
+    #[must_use]
+    pub fn into_path_buf(self) -> PathBuf {
+        self.0
+    }
+
+    /// Equivalent to [`RelPath::parent`] but modifies `self` in place.
+    ///
+    /// Returns [`false`] when [`RelPath::parent`] returns [`None`].
+    #[inline]
+    pub fn pop(&mut self) -> bool {
+        self.0.pop()
+    }
+
+    /// Equivalent to [`RelPath::join`] but modifies `self` in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use normpath::RelPath;
+    /// use normpath::RelPathBuf;
+    ///
+    /// let mut path = RelPathBuf::new();
+    /// path.push(RelPath::try_new("foo").unwrap());
+    /// path.push(RelPath::try_new("bar").unwrap());
+    /// assert_eq!(Path::new("foo").join("bar"), path.as_path());
+    /// ```
+    #[inline]
+    pub fn push(&mut self, path: &RelPath) {
+        // Pushing an empty path would add a trailing separator.
+        if !path.is_empty() {
+            self.0.push(path);
+        }
+    }
+}
+
+impl AsRef<OsStr> for RelPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl AsRef<Path> for RelPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<RelPath> for RelPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &RelPath {
+        self
+    }
+}
+
+impl Borrow<RelPath> for RelPathBuf {
+    #[inline]
+    fn borrow(&self) -> &RelPath {
+        self
+    }
+}
+
+impl Deref for RelPathBuf {
+    type Target = RelPath;
+
+    #[inline]
+    fn deref(&self) -> &RelPath {
+        RelPath::from_inner(self.0.as_os_str())
+    }
+}
+
+impl Eq for RelPathBuf {}
+
+impl From<RelPathBuf> for OsString {
+    #[inline]
+    fn from(value: RelPathBuf) -> Self {
+        value.into_os_string()
+    }
+}
+
+impl From<RelPathBuf> for PathBuf {
+    #[inline]
+    fn from(value: RelPathBuf) -> Self {
+        value.0
+    }
+}
+
+impl Hash for RelPathBuf {
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        (**self).hash(state);
+    }
+}
+
+impl Ord for RelPathBuf {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl PartialEq for RelPathBuf {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+#[expect(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for RelPathBuf {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
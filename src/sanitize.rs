@@ -0,0 +1,290 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::iter;
+use std::str;
+
+use super::lexical;
+use super::validate::WINDOWS_FORBIDDEN_CHARS;
+use super::Platform;
+
+/// Options for [`sanitize_file_name`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+///
+/// use normpath::Platform;
+/// use normpath::SanitizeOptions;
+///
+/// let options = SanitizeOptions::new()
+///     .platform(Platform::Linux)
+///     .replacement('-')
+///     .max_len(16);
+/// assert_eq!(
+///     OsStr::new("a-b-very-lon.tar"),
+///     normpath::sanitize_file_name(OsStr::new("a/b\0very-long.tar"), options),
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SanitizeOptions {
+    platform: Platform,
+    replacement: char,
+    max_len: usize,
+}
+
+impl SanitizeOptions {
+    /// Creates options that sanitize names for [`Platform::Portable`] using
+    /// `_` as the replacement character and a limit of 255 bytes.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            platform: Platform::Portable,
+            replacement: '_',
+            max_len: 255,
+        }
+    }
+
+    /// Sets the platform that names must be valid for.
+    ///
+    /// The rules for each platform are the same as those used by
+    /// [`BasePath::validate_for`].
+    ///
+    /// [`BasePath::validate_for`]: super::BasePath::validate_for
+    #[inline]
+    #[must_use]
+    pub fn platform(mut self, platform: Platform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Sets the character used to replace invalid characters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the character would be invalid in a name for
+    /// [`Platform::Portable`], or if it is a period or space.
+    #[inline]
+    #[must_use]
+    pub fn replacement(mut self, replacement: char) -> Self {
+        assert!(
+            !is_forbidden(replacement, true)
+                && !matches!(replacement, '.' | ' '),
+            "invalid replacement character: {:?}",
+            replacement,
+        );
+        self.replacement = replacement;
+        self
+    }
+
+    /// Sets the maximum length of names in bytes.
+    ///
+    /// Names are truncated to this length, keeping their extension if
+    /// possible. Since a name is never empty, it can only exceed this limit if
+    /// the limit is shorter than the replacement character.
+    #[inline]
+    #[must_use]
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl Default for SanitizeOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_forbidden(ch: char, windows: bool) -> bool {
+    matches!(ch, '/' | '\\')
+        || ch.is_control()
+        || (windows
+            && ch.is_ascii()
+            && WINDOWS_FORBIDDEN_CHARS.contains(&(ch as u8)))
+}
+
+fn is_continuation(byte: u8) -> bool {
+    (0x80..0xC0).contains(&byte)
+}
+
+// Returns the length of the sequence at the start of the bytes. Sequences are
+// parsed by their structure, so unpaired surrogates encoded by
+// [`OsStr::as_encoded_bytes`] on Windows are kept together. Any other invalid
+// byte is its own sequence.
+fn sequence_len(bytes: &[u8]) -> usize {
+    let len = match bytes[0] {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return 1,
+    };
+    if bytes
+        .get(1..len)
+        .is_some_and(|x| x.iter().all(|&x| is_continuation(x)))
+    {
+        len
+    } else {
+        1
+    }
+}
+
+fn sequences(mut bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    iter::from_fn(move || {
+        if bytes.is_empty() {
+            return None;
+        }
+        let sequence;
+        (sequence, bytes) = bytes.split_at(sequence_len(bytes));
+        Some(sequence)
+    })
+}
+
+// Returns the length of a prefix of the name that does not end within a
+// sequence.
+fn floor_char_boundary(name: &[u8], index: usize) -> usize {
+    let mut start = 0;
+    for sequence in sequences(name) {
+        let end = start + sequence.len();
+        if index < end {
+            return start;
+        }
+        start = end;
+    }
+    index
+}
+
+fn truncate(name: &mut Vec<u8>, max_len: usize) {
+    if name.len() <= max_len {
+        return;
+    }
+
+    let extension = name
+        .iter()
+        .rposition(|&x| x == b'.')
+        .filter(|&x| x != 0 && name.len() - x < max_len)
+        .map(|x| name.split_off(x))
+        .unwrap_or_default();
+    let len = floor_char_boundary(name, max_len - extension.len());
+    if len == 0 {
+        name.extend(extension);
+        name.truncate(floor_char_boundary(name, max_len));
+    } else {
+        name.truncate(len);
+        name.extend(extension);
+    }
+}
+
+pub(super) fn sanitize(
+    name: &OsStr,
+    options: &SanitizeOptions,
+    windows: bool,
+) -> OsString {
+    let windows = windows || options.platform.is_windows();
+    let mut replacement = [0; 4];
+    let replacement = options.replacement.encode_utf8(&mut replacement);
+
+    let mut result = Vec::with_capacity(name.len());
+    for sequence in sequences(name.as_encoded_bytes()) {
+        let replace = if let Ok(ch) = str::from_utf8(sequence) {
+            ch.chars().any(|x| is_forbidden(x, windows))
+        } else {
+            options.platform.requires_utf8()
+        };
+        if replace {
+            result.extend(replacement.as_bytes());
+        } else {
+            result.extend(sequence);
+        }
+    }
+
+    truncate(&mut result, options.max_len);
+    // These names must be checked after truncation, since it can create
+    // them. Removing trailing periods below cannot. The replacement is
+    // shortened if necessary to respect the limit.
+    if matches!(&*result, b"." | b"..") {
+        let count =
+            (options.max_len / replacement.len()).clamp(1, result.len());
+        result = replacement.repeat(count).into_bytes();
+    }
+    if windows {
+        while result.ends_with(b".") || result.ends_with(b" ") {
+            let _ = result.pop();
+        }
+
+        // Replacing the last character of the device name prevents it from
+        // being reserved without increasing its length.
+        if lexical::is_reserved_name(&result) {
+            let end = result
+                .iter()
+                .position(|&x| x == b'.')
+                .unwrap_or(result.len());
+            let end = result[..end].trim_ascii_end().len();
+            let start = floor_char_boundary(&result, end - 1);
+            let _ = result.splice(start..end, replacement.bytes());
+        }
+    }
+    if result.is_empty() {
+        result.extend(replacement.as_bytes());
+    }
+
+    // SAFETY: The bytes were copied from the name or a string, and they were
+    // only split between complete sequences. Since the encoding is
+    // self-synchronizing, no sequence was split or combined with another.
+    unsafe { OsString::from_encoded_bytes_unchecked(result) }
+}
+
+/// Converts an untrusted name to a name that can be used for a file in any
+/// directory.
+///
+/// These changes are made to the name:
+/// - Separators, control characters, and characters that cannot be used on
+///   the [platform] are replaced by the [replacement character].
+/// - If the platform requires UTF-8, invalid sequences are replaced by the
+///   replacement character.
+/// - The name is truncated to the [maximum length], keeping the extension if
+///   it is shorter than that length.
+/// - The names `.` and `..` are replaced by the same number of replacement
+///   characters, or fewer if necessary to respect the maximum length.
+/// - On Windows, trailing periods and spaces are removed, and the last
+///   character of a reserved device name is replaced.
+/// - An empty name is replaced by the replacement character.
+///
+/// The result will never contain a separator, and it will never be `.` or
+/// `..`.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+///
+/// use normpath::SanitizeOptions;
+///
+/// #[track_caller]
+/// fn test(result: &str, name: &str) {
+///     assert_eq!(
+///         OsStr::new(result),
+///         normpath::sanitize_file_name(
+///             OsStr::new(name),
+///             SanitizeOptions::new(),
+///         ),
+///     );
+/// }
+///
+/// test("_.._etc_passwd", "/../etc/passwd");
+/// test("report_ final.pdf", "report: final.pdf.. ");
+/// test("NU_.txt", "NUL.txt");
+/// test("__", "..");
+/// test("_", "");
+/// ```
+///
+/// [maximum length]: SanitizeOptions::max_len
+/// [platform]: SanitizeOptions::platform
+/// [replacement character]: SanitizeOptions::replacement
+#[inline]
+#[must_use]
+pub fn sanitize_file_name(name: &OsStr, options: SanitizeOptions) -> OsString {
+    sanitize(name, &options, false)
+}
@@ -0,0 +1,266 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+
+use super::lexical;
+use super::lexical::os_str;
+use super::lexical::Prefix;
+
+/// The POSIX environment that a [`PosixTranslator`] translates paths for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PosixStyle {
+    /// [Cygwin], which mounts drives in "/cygdrive" by default and writes UNC
+    /// paths as `//server/share`.
+    ///
+    /// [Cygwin]: https://cygwin.com/cygwin-ug-net/using.html#using-pathnames
+    Cygwin,
+
+    /// [MSYS2], including Git Bash, which mounts drives in "/" by default and
+    /// writes UNC paths as `//server/share`.
+    ///
+    /// [MSYS2]: https://www.msys2.org/docs/filesystem-paths/
+    Msys,
+
+    /// The [Windows Subsystem for Linux], which mounts drives in "/mnt" by
+    /// default. Other paths are available to Windows using the `\\wsl$` UNC
+    /// server.
+    ///
+    /// [Windows Subsystem for Linux]: https://learn.microsoft.com/en-us/windows/wsl/filesystems
+    Wsl,
+}
+
+/// Lexically translates between Windows paths and POSIX paths used by an
+/// environment running on Windows.
+///
+/// Translation never accesses the file system, and it interprets Windows
+/// paths using Windows syntax on every platform. Therefore, it can be used
+/// on either side of the translation. Translated paths are normalized
+/// lexically, and only absolute paths can be translated.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use normpath::BasePath;
+/// use normpath::BasePathBuf;
+/// use normpath::PosixStyle;
+/// use normpath::PosixTranslator;
+///
+/// let translator = PosixTranslator::new(PosixStyle::Wsl).mount_root("/");
+///
+/// let path = BasePath::try_new(r"X:\foo\bar").unwrap();
+/// assert_eq!(
+///     Some(Path::new("/x/foo/bar")),
+///     path.to_posix_with(&translator).as_deref(),
+/// );
+///
+/// let path = BasePathBuf::from_posix_with("/x/foo/bar", &translator);
+/// assert_eq!(
+///     Some(Path::new(r"X:\foo\bar")),
+///     path.as_ref().map(|x| x.as_path()),
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PosixTranslator {
+    style: PosixStyle,
+    mount_root: OsString,
+    wsl_distro: Option<OsString>,
+}
+
+impl PosixTranslator {
+    /// Creates a translator that uses the default configuration for `style`.
+    #[inline]
+    #[must_use]
+    pub fn new(style: PosixStyle) -> Self {
+        Self {
+            style,
+            mount_root: match style {
+                PosixStyle::Cygwin => "/cygdrive",
+                PosixStyle::Msys => "/",
+                PosixStyle::Wsl => "/mnt",
+            }
+            .into(),
+            wsl_distro: None,
+        }
+    }
+
+    /// Sets the directory that contains a mount point for each drive.
+    ///
+    /// This value is configured using `automount.root` in "wsl.conf" for WSL
+    /// and the `cygdrive` entry in "/etc/fstab" for Cygwin and MSYS2.
+    #[inline]
+    #[must_use]
+    pub fn mount_root<P>(mut self, mount_root: P) -> Self
+    where
+        P: Into<OsString>,
+    {
+        self.mount_root = mount_root.into();
+        self
+    }
+
+    /// Sets the name of the WSL distribution, such as "Ubuntu".
+    ///
+    /// If this value is set and the [style] is [`PosixStyle::Wsl`], absolute
+    /// POSIX paths that are not in the [mount root] will be translated to
+    /// paths using the `\\wsl$` UNC server. Additionally, only paths using
+    /// that server for this distribution will be translated to POSIX paths.
+    ///
+    /// [mount root]: Self::mount_root
+    /// [style]: PosixStyle
+    #[inline]
+    #[must_use]
+    pub fn wsl_distro<D>(mut self, wsl_distro: D) -> Self
+    where
+        D: Into<OsString>,
+    {
+        self.wsl_distro = Some(wsl_distro.into());
+        self
+    }
+
+    fn mount_root_components(&self) -> Vec<&[u8]> {
+        normalize(self.mount_root.as_encoded_bytes(), b'/', false)
+            .unwrap_or_default()
+    }
+
+    fn is_wsl_server(&self, server: &[u8], share: &[u8]) -> bool {
+        self.style == PosixStyle::Wsl
+            && [&b"wsl$"[..], b"wsl.localhost"]
+                .iter()
+                .any(|x| x.eq_ignore_ascii_case(server))
+            && self.wsl_distro.as_ref().map_or(true, |x| {
+                x.as_encoded_bytes().eq_ignore_ascii_case(share)
+            })
+    }
+}
+
+// Removes empty, `.`, and `..` components lexically, which is how Windows
+// and the translated environments handle them for absolute paths. Verbatim
+// paths cannot contain these components.
+fn normalize(
+    path: &[u8],
+    separator: u8,
+    verbatim: bool,
+) -> Option<Vec<&[u8]>> {
+    let mut components = Vec::new();
+    for component in path.split(|&x| {
+        x == separator || (!verbatim && separator == b'\\' && x == b'/')
+    }) {
+        match component {
+            b"" => {}
+            b"." | b".." if verbatim => return None,
+            b"." => {}
+            b".." => {
+                let _ = components.pop();
+            }
+            _ => components.push(component),
+        }
+    }
+    Some(components)
+}
+
+fn join(
+    mut result: OsString,
+    components: &[&[u8]],
+    separator: &str,
+) -> OsString {
+    for component in components {
+        result.push(separator);
+        result.push(os_str(component));
+    }
+    result
+}
+
+pub(super) fn to_posix(
+    path: &OsStr,
+    translator: &PosixTranslator,
+) -> Option<OsString> {
+    let (prefix, path) = lexical::parse_prefix(path.as_encoded_bytes())?;
+    let mut result = OsString::new();
+    match prefix {
+        Prefix::Disk(drive) | Prefix::VerbatimDisk(drive) => {
+            // Drive-relative paths cannot be translated.
+            if !path.first().copied().is_some_and(lexical::is_separator) {
+                return None;
+            }
+            result = join(result, &translator.mount_root_components(), "/");
+            result.push("/");
+            result.push(os_str(&[drive.to_ascii_lowercase()]));
+        }
+        Prefix::Unc(server, share) | Prefix::VerbatimUnc(server, share) => {
+            if !translator.is_wsl_server(server, share) {
+                if translator.style == PosixStyle::Wsl {
+                    return None;
+                }
+                result.push("/");
+                result = join(result, &[server, share], "/");
+            }
+        }
+        _ => return None,
+    }
+
+    let components = normalize(path, b'\\', prefix.is_verbatim())?;
+    if components.iter().any(|x| x.contains(&b'/')) {
+        return None;
+    }
+    result = join(result, &components, "/");
+    if result.is_empty() {
+        result.push("/");
+    }
+    Some(result)
+}
+
+pub(super) fn from_posix(
+    path: &OsStr,
+    translator: &PosixTranslator,
+) -> Option<OsString> {
+    let path = path.as_encoded_bytes();
+    if !path.starts_with(b"/") {
+        return None;
+    }
+
+    let mut result;
+    let components;
+    // Two leading separators are used for UNC paths, but more than two should
+    // be treated as a single separator.
+    if let Some(path) = path.strip_prefix(b"//").filter(|x| {
+        translator.style != PosixStyle::Wsl && !x.starts_with(b"/")
+    }) {
+        let mut path = path.splitn(3, |&x| x == b'/');
+        let server = path.next().unwrap_or_default();
+        let share = path.next().unwrap_or_default();
+        if server.is_empty() || share.is_empty() {
+            return None;
+        }
+        result = join(r"\".into(), &[server, share], r"\");
+        components = normalize(path.next().unwrap_or_default(), b'/', false)?;
+    } else {
+        let path = normalize(path, b'/', false)?;
+        let mount_root = translator.mount_root_components();
+        match path.strip_prefix(&mount_root[..]) {
+            Some([[drive], path @ ..]) if drive.is_ascii_alphabetic() => {
+                result = os_str(&[drive.to_ascii_uppercase(), b':']).into();
+                components = path.to_vec();
+            }
+            _ => {
+                let distro = translator
+                    .wsl_distro
+                    .as_ref()
+                    .filter(|_| translator.style == PosixStyle::Wsl)?;
+                result = r"\\wsl$\".into();
+                result.push(distro);
+                components = path;
+            }
+        }
+    }
+
+    if components.iter().any(|x| x.contains(&b'\\')) {
+        return None;
+    }
+    result = join(result, &components, r"\");
+    // Drive prefixes must be followed by a separator to be absolute.
+    if components.is_empty() && result.len() == 2 {
+        result.push(r"\");
+    }
+    Some(result)
+}
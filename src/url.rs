@@ -0,0 +1,212 @@
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::path::Component;
+use std::path::PathBuf;
+use std::path::Prefix;
+
+use super::error::FileUrlError;
+use super::BasePath;
+use super::BasePathBuf;
+
+const SCHEME: &str = "file:";
+
+// Characters that can be used in a path segment without encoding, excluding
+// alphanumeric characters.
+const SEGMENT_CHARS: &[u8] = b"-._~!$&'()*+,;=:@";
+
+#[cfg(unix)]
+fn os_str_bytes(string: &OsStr) -> Option<&[u8]> {
+    Some(string.as_encoded_bytes())
+}
+
+// Other platforms do not have a byte representation that can be reliably
+// converted back.
+#[cfg(not(unix))]
+fn os_str_bytes(string: &OsStr) -> Option<&[u8]> {
+    string.to_str().map(str::as_bytes)
+}
+
+#[cfg(unix)]
+fn bytes_os_string(string: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+
+    Some(OsString::from_vec(string))
+}
+
+#[cfg(not(unix))]
+fn bytes_os_string(string: Vec<u8>) -> Option<OsString> {
+    String::from_utf8(string).ok().map(Into::into)
+}
+
+fn is_separator(byte: u8) -> bool {
+    byte == b'/' || (cfg!(windows) && byte == b'\\')
+}
+
+fn encode(url: &mut String, string: &OsStr) -> Result<(), FileUrlError> {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    let string = os_str_bytes(string).ok_or(FileUrlError::InvalidPath)?;
+    for &byte in string {
+        if byte.is_ascii_alphanumeric() || SEGMENT_CHARS.contains(&byte) {
+            url.push(byte.into());
+        } else {
+            url.push('%');
+            url.push(HEX_DIGITS[usize::from(byte >> 4)].into());
+            url.push(HEX_DIGITS[usize::from(byte & 0xF)].into());
+        }
+    }
+    Ok(())
+}
+
+fn decode(string: &str) -> Result<Vec<u8>, FileUrlError> {
+    fn hex_digit(byte: Option<&u8>) -> Result<u8, FileUrlError> {
+        byte.and_then(|&x| char::from(x).to_digit(16))
+            .map(|x| x as u8)
+            .ok_or(FileUrlError::InvalidPercentEncoding)
+    }
+
+    let mut result = Vec::with_capacity(string.len());
+    let mut bytes = string.as_bytes().iter();
+    while let Some(&byte) = bytes.next() {
+        result.push(if byte == b'%' {
+            (hex_digit(bytes.next())? << 4) | hex_digit(bytes.next())?
+        } else {
+            byte
+        });
+    }
+    Ok(result)
+}
+
+pub(super) fn to_file_url(path: &BasePath) -> Result<String, FileUrlError> {
+    let mut url = SCHEME.to_owned();
+    url.push_str("//");
+
+    let mut components = path.components();
+    // Paths that consist of only a root should end with a separator.
+    let mut requires_separator = true;
+    match components.next() {
+        Some(Component::Prefix(prefix)) => {
+            match prefix.kind() {
+                Prefix::Disk(drive) | Prefix::VerbatimDisk(drive) => {
+                    url.push('/');
+                    url.push(drive.into());
+                    url.push(':');
+                }
+                Prefix::UNC(server, share)
+                | Prefix::VerbatimUNC(server, share) => {
+                    encode(&mut url, server)?;
+                    url.push('/');
+                    encode(&mut url, share)?;
+                    requires_separator = false;
+                }
+                _ => return Err(FileUrlError::UnsupportedPath),
+            }
+            let mut next_components = components.clone();
+            if next_components.next() == Some(Component::RootDir) {
+                components = next_components;
+            } else if requires_separator {
+                return Err(FileUrlError::UnsupportedPath);
+            }
+        }
+        Some(Component::RootDir) => {}
+        _ => return Err(FileUrlError::UnsupportedPath),
+    }
+
+    for component in components {
+        url.push('/');
+        encode(&mut url, component.as_os_str())?;
+        requires_separator = false;
+    }
+    if requires_separator
+        || path
+            .as_os_str()
+            .as_encoded_bytes()
+            .last()
+            .is_some_and(|&x| is_separator(x))
+    {
+        url.push('/');
+    }
+    Ok(url)
+}
+
+pub(super) fn from_file_url(url: &str) -> Result<BasePathBuf, FileUrlError> {
+    let url = url
+        .get(..SCHEME.len())
+        .filter(|x| x.eq_ignore_ascii_case(SCHEME))
+        .map(|_| &url[SCHEME.len()..])
+        .ok_or(FileUrlError::InvalidScheme)?;
+    if url.contains(['?', '#']) {
+        return Err(FileUrlError::QueryOrFragment);
+    }
+
+    let (host, path) = if let Some(url) = url.strip_prefix("//") {
+        url.split_at(url.find('/').unwrap_or(url.len()))
+    } else {
+        ("", url)
+    };
+    let mut host = decode(host)?;
+    if host.eq_ignore_ascii_case(b"localhost") {
+        host.clear();
+    }
+
+    let path = match path {
+        "" => "/",
+        _ if path.starts_with('/') => path,
+        _ => return Err(FileUrlError::InvalidPath),
+    };
+    let mut segments = path[1..]
+        .split('/')
+        .map(|segment| {
+            let segment = decode(segment)?;
+            if segment.iter().any(|&x| x == 0 || is_separator(x)) {
+                return Err(FileUrlError::InvalidPath);
+            }
+            Ok(segment)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let result = if cfg!(windows) {
+        // Paths such as "file:////server/share" contain the UNC prefix in the
+        // path.
+        if host.is_empty() && segments.len() > 2 && segments[0].is_empty() {
+            host = segments.remove(1);
+            let _ = segments.remove(0);
+        }
+
+        let mut result = Vec::new();
+        if host.is_empty() {
+            match segments.first_mut().map(|x| &mut x[..]) {
+                Some([drive, separator @ (b':' | b'|')])
+                    if drive.is_ascii_alphabetic() =>
+                {
+                    *separator = b':';
+                }
+                _ => return Err(FileUrlError::InvalidPath),
+            }
+            // Drive prefixes must be followed by a separator to be absolute.
+            if segments.len() == 1 {
+                segments.push(Vec::new());
+            }
+        } else {
+            if segments.first().map_or(true, Vec::is_empty) {
+                return Err(FileUrlError::InvalidPath);
+            }
+            result.extend(b"\\\\");
+            result.append(&mut host);
+            result.push(b'\\');
+        }
+        result.append(&mut segments.join(&b'\\'));
+        result
+    } else {
+        if !host.is_empty() {
+            return Err(FileUrlError::InvalidHost);
+        }
+        let mut result = segments.join(&b'/');
+        result.insert(0, b'/');
+        result
+    };
+
+    let path = bytes_os_string(result).ok_or(FileUrlError::InvalidPath)?;
+    BasePathBuf::try_new(PathBuf::from(path))
+        .map_err(|_| FileUrlError::InvalidPath)
+}
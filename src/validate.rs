@@ -0,0 +1,113 @@
+use std::path::Component;
+use std::str;
+
+use super::error::InvalidComponent;
+use super::error::InvalidComponentKind;
+use super::error::ValidationError;
+use super::lexical;
+use super::BasePath;
+
+// Characters that cannot be used in file names on Windows, excluding control
+// characters.
+pub(super) const WINDOWS_FORBIDDEN_CHARS: &[u8] = br#""*/:<>?\|"#;
+
+const MAX_NAME_LEN: usize = 255;
+
+/// A platform with rules for which file names are valid.
+///
+/// The rules for each platform are described by [`BasePath::validate_for`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Platform {
+    /// Linux, where only the null character is forbidden and names can have
+    /// up to 255 bytes.
+    Linux,
+
+    /// MacOS, where names must be valid UTF-8 and can have up to 255 bytes.
+    MacOs,
+
+    /// Any of the other platforms. A name is only valid if it is valid for
+    /// all of them.
+    Portable,
+
+    /// Windows, where reserved names, some punctuation, control characters,
+    /// and trailing periods and spaces are forbidden. Names can have up to
+    /// 255 UTF-16 code units.
+    Windows,
+}
+
+impl Platform {
+    pub(super) fn is_windows(self) -> bool {
+        matches!(self, Self::Portable | Self::Windows)
+    }
+
+    pub(super) fn requires_utf8(self) -> bool {
+        matches!(self, Self::MacOs | Self::Portable)
+    }
+}
+
+// Returns the reasons that a file name is invalid for the platform, in the
+// order that they are declared.
+pub(super) fn check_name(
+    name: &[u8],
+    platform: Platform,
+) -> Vec<InvalidComponentKind> {
+    let windows = platform.is_windows();
+
+    let mut kinds = Vec::new();
+    if windows && lexical::is_reserved_name(name) {
+        kinds.push(InvalidComponentKind::ReservedName);
+    }
+    if windows {
+        if let Some(&ch) =
+            name.iter().find(|x| WINDOWS_FORBIDDEN_CHARS.contains(x))
+        {
+            kinds.push(InvalidComponentKind::ForbiddenCharacter(ch.into()));
+        }
+    }
+    if let Some(&ch) = name.iter().find(|&&x| x == 0 || (windows && x < 0x20))
+    {
+        kinds.push(InvalidComponentKind::ControlCharacter(ch.into()));
+    }
+    if windows && (name.ends_with(b".") || name.ends_with(b" ")) {
+        kinds.push(InvalidComponentKind::TrailingDotOrSpace);
+    }
+    if platform.requires_utf8() && str::from_utf8(name).is_err() {
+        kinds.push(InvalidComponentKind::InvalidEncoding);
+    }
+    let len = if platform == Platform::Windows {
+        lexical::utf16_len(name)
+    } else {
+        name.len()
+    };
+    if len > MAX_NAME_LEN {
+        kinds.push(InvalidComponentKind::TooLong);
+    }
+    kinds
+}
+
+pub(super) fn validate(
+    path: &BasePath,
+    platform: Platform,
+) -> Result<(), ValidationError> {
+    let mut components = Vec::new();
+    for (index, component) in path.components().enumerate() {
+        if let Component::Normal(name) = component {
+            components.extend(
+                check_name(name.as_encoded_bytes(), platform)
+                    .into_iter()
+                    .map(|kind| InvalidComponent {
+                        index,
+                        name: name.to_owned(),
+                        kind,
+                    }),
+            );
+        }
+    }
+
+    if components.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError(components))
+    }
+}
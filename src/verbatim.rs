@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+
+use super::lexical;
+use super::lexical::os_str;
+use super::lexical::Prefix;
+
+// The terminating null character is included in this limit.
+const MAX_PATH: usize = 260;
+
+// Normalizes the components after a prefix using the same rules as
+// GetFullPathNameW. Components cannot be removed above the root.
+fn normalize(path: &[u8]) -> Vec<&[u8]> {
+    let mut components = Vec::new();
+    for component in path.split(|&x| lexical::is_separator(x)) {
+        match component {
+            b"" | b"." => {}
+            b".." => {
+                let _ = components.pop();
+            }
+            // A single trailing period is removed from each component.
+            [.., x, b'.'] if *x != b'.' => {
+                components.push(&component[..component.len() - 1]);
+            }
+            _ => components.push(component),
+        }
+    }
+
+    // All trailing periods and spaces are removed if the path does not end
+    // with a separator.
+    if !path.last().copied().is_some_and(lexical::is_separator) {
+        if let Some(component) = components.pop() {
+            let end = component
+                .iter()
+                .rposition(|&x| x != b'.' && x != b' ')
+                .map_or(0, |x| x + 1);
+            if end != 0 {
+                components.push(&component[..end]);
+            }
+        }
+    }
+    components
+}
+
+pub(super) fn to_verbatim(path: &OsStr) -> Option<Cow<'_, OsStr>> {
+    let (prefix, rest) = lexical::parse_prefix(path.as_encoded_bytes())?;
+    let mut result = OsString::from(r"\\?\");
+    match prefix {
+        _ if prefix.is_verbatim() => return Some(Cow::Borrowed(path)),
+        Prefix::Disk(drive) => {
+            // Drive-relative paths cannot be converted lexically.
+            if !rest.first().copied().is_some_and(lexical::is_separator) {
+                return None;
+            }
+            result.push(os_str(&[drive, b':']));
+        }
+        Prefix::Unc(server, share) => {
+            result.push(r"UNC\");
+            result.push(os_str(server));
+            result.push(r"\");
+            result.push(os_str(share));
+        }
+        Prefix::DeviceNs(device) => result.push(os_str(device)),
+        _ => unreachable!(),
+    }
+
+    let components = normalize(rest);
+    // The last component would otherwise refer to a device.
+    if !matches!(prefix, Prefix::DeviceNs(_))
+        && components
+            .last()
+            .is_some_and(|x| lexical::is_reserved_name(x))
+    {
+        return None;
+    }
+    for component in &components {
+        result.push(r"\");
+        result.push(os_str(component));
+    }
+    // Trailing separators are preserved, and drive prefixes must be followed
+    // by a separator to be absolute.
+    if (!components.is_empty()
+        && rest.last().copied().is_some_and(lexical::is_separator))
+        || (components.is_empty() && matches!(prefix, Prefix::Disk(_)))
+    {
+        result.push(r"\");
+    }
+    Some(Cow::Owned(result))
+}
+
+// Returns whether a component of a verbatim path would be interpreted the
+// same way without the verbatim prefix.
+fn is_simple_component(component: &[u8]) -> bool {
+    !matches!(component, b"" | b"." | b"..")
+        && !component.contains(&b'/')
+        && !component.ends_with(b".")
+        && !component.ends_with(b" ")
+        && !lexical::is_reserved_name(component)
+}
+
+pub(super) fn simplify(path: &OsStr) -> Option<Cow<'_, OsStr>> {
+    let bytes = path.as_encoded_bytes();
+    let (result, rest) = match lexical::parse_prefix(bytes)? {
+        (Prefix::VerbatimDisk(_), rest) => {
+            // Without a root, this prefix refers to the volume.
+            if rest.is_empty() {
+                return None;
+            }
+            (Cow::Borrowed(os_str(&bytes[4..])), rest)
+        }
+        (Prefix::VerbatimUnc(server, share), rest)
+            if is_simple_component(server) && is_simple_component(share) =>
+        {
+            let mut result = OsString::from(r"\");
+            result.push(os_str(&bytes[7..]));
+            (Cow::Owned(result), rest)
+        }
+        _ => return None,
+    };
+
+    if let Some(rest) = rest.strip_prefix(br"\") {
+        // A trailing separator has the same meaning in both forms.
+        let rest = rest.strip_suffix(br"\").unwrap_or(rest);
+        if !rest.is_empty()
+            && !rest.split(|&x| x == b'\\').all(is_simple_component)
+        {
+            return None;
+        }
+    }
+    (lexical::utf16_len(result.as_encoded_bytes()) < MAX_PATH)
+        .then_some(result)
+}
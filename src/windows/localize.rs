@@ -1,17 +1,44 @@
 use std::ffi::OsString;
+use std::io;
 use std::os::windows::ffi::OsStrExt;
 use std::os::windows::ffi::OsStringExt;
 use std::path::Path;
+use std::path::PathBuf;
 use std::ptr;
+use std::slice;
 
+use windows_sys::core::GUID;
+use windows_sys::Win32::System::Com::CoTaskMemFree;
+use windows_sys::Win32::UI::Shell::FOLDERID_Desktop;
+use windows_sys::Win32::UI::Shell::FOLDERID_Documents;
+use windows_sys::Win32::UI::Shell::FOLDERID_Downloads;
+use windows_sys::Win32::UI::Shell::FOLDERID_LocalAppData;
+use windows_sys::Win32::UI::Shell::FOLDERID_Music;
+use windows_sys::Win32::UI::Shell::FOLDERID_Pictures;
+use windows_sys::Win32::UI::Shell::FOLDERID_Profile;
+use windows_sys::Win32::UI::Shell::FOLDERID_Public;
+use windows_sys::Win32::UI::Shell::FOLDERID_RoamingAppData;
+use windows_sys::Win32::UI::Shell::FOLDERID_Templates;
+use windows_sys::Win32::UI::Shell::FOLDERID_Videos;
 use windows_sys::Win32::UI::Shell::SHGetFileInfoW;
+use windows_sys::Win32::UI::Shell::SHGetKnownFolderPath;
+use windows_sys::Win32::UI::Shell::KF_FLAG_DEFAULT;
 use windows_sys::Win32::UI::Shell::SHFILEINFOW;
 use windows_sys::Win32::UI::Shell::SHGFI_DISPLAYNAME;
+use windows_sys::Win32::UI::Shell::SHGFI_FLAGS;
+use windows_sys::Win32::UI::Shell::SHGFI_TYPENAME;
 
-pub(crate) fn name(path: &Path) -> Option<OsString> {
+use crate::error::LocalizeError;
+use crate::EnvProvider;
+use crate::KnownFolder;
+
+fn file_info(
+    path: &Path,
+    flags: SHGFI_FLAGS,
+) -> Result<SHFILEINFOW, LocalizeError> {
     let mut path: Vec<_> = path.as_os_str().encode_wide().collect();
     if path.contains(&0) {
-        return None;
+        return Err(LocalizeError::InvalidPath);
     }
     path.push(0);
 
@@ -30,20 +57,136 @@ pub(crate) fn name(path: &Path) -> Option<OsString> {
             size_of_val(&path_info)
                 .try_into()
                 .expect("path information too large for WinAPI"),
-            SHGFI_DISPLAYNAME,
+            flags,
         )
     };
     if result == 0 {
-        return None;
+        return Err(LocalizeError::Failed);
     }
+    Ok(path_info)
+}
 
-    // The display name buffer has a fixed length, so it must be truncated at
-    // the first null character.
-    Some(OsString::from_wide(
-        path_info
-            .szDisplayName
+// The buffers in [SHFILEINFOW] have a fixed length, so they must be truncated
+// at the first null character.
+fn from_buffer(buffer: &[u16]) -> OsString {
+    OsString::from_wide(
+        buffer
             .split(|&x| x == 0)
             .next()
-            .expect("missing null byte in display name"),
-    ))
+            .expect("missing null byte in buffer"),
+    )
+}
+
+fn name(path: &Path) -> Result<OsString, LocalizeError> {
+    file_info(path, SHGFI_DISPLAYNAME).map(|x| from_buffer(&x.szDisplayName))
+}
+
+// Localizes names using state that can be reused for multiple paths.
+pub(crate) struct Localizer {
+    // The system API can only use the language of the system.
+    system_locale: bool,
+}
+
+impl Localizer {
+    pub(crate) fn new<E>(_: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        Self {
+            system_locale: true,
+        }
+    }
+
+    pub(crate) fn with_locale<E>(_: &str, _: &E) -> Self
+    where
+        E: EnvProvider + ?Sized,
+    {
+        Self {
+            system_locale: false,
+        }
+    }
+
+    pub(crate) fn name(
+        &self,
+        path: &Path,
+    ) -> Result<Option<OsString>, LocalizeError> {
+        if !self.system_locale {
+            return Err(LocalizeError::Unsupported);
+        }
+        name(path).map(Some)
+    }
+
+    pub(crate) fn type_name(&self, path: &Path) -> Option<OsString> {
+        if !self.system_locale {
+            return None;
+        }
+        file_info(path, SHGFI_TYPENAME)
+            .ok()
+            .map(|x| from_buffer(&x.szTypeName))
+            .filter(|x| !x.is_empty())
+    }
+}
+
+// Shell functions return an HRESULT, which only contains a system error code
+// when its facility is FACILITY_WIN32.
+fn hresult_error(result: i32) -> io::Error {
+    const FACILITY_WIN32: u32 = 7;
+
+    let result = result as u32;
+    if (result >> 16) & 0x1FFF == FACILITY_WIN32 {
+        io::Error::from_raw_os_error((result & 0xFFFF) as i32)
+    } else {
+        io::Error::other(format!(
+            "failed to retrieve known folder (HRESULT {:#010X})",
+            result,
+        ))
+    }
+}
+
+pub(crate) fn known_folder<E>(
+    folder: KnownFolder,
+    _: &E,
+) -> io::Result<PathBuf>
+where
+    E: EnvProvider + ?Sized,
+{
+    let id: &GUID = match folder {
+        KnownFolder::Cache => &FOLDERID_LocalAppData,
+        KnownFolder::Config | KnownFolder::Data => &FOLDERID_RoamingAppData,
+        KnownFolder::Desktop => &FOLDERID_Desktop,
+        KnownFolder::Documents => &FOLDERID_Documents,
+        KnownFolder::Downloads => &FOLDERID_Downloads,
+        KnownFolder::Home => &FOLDERID_Profile,
+        KnownFolder::Music => &FOLDERID_Music,
+        KnownFolder::Pictures => &FOLDERID_Pictures,
+        KnownFolder::Public => &FOLDERID_Public,
+        KnownFolder::Templates => &FOLDERID_Templates,
+        KnownFolder::Videos => &FOLDERID_Videos,
+    };
+
+    let mut path = ptr::null_mut();
+    let result = unsafe {
+        SHGetKnownFolderPath(
+            id,
+            KF_FLAG_DEFAULT as _,
+            ptr::null_mut(),
+            &mut path,
+        )
+    };
+    let result = if result == 0 {
+        // SAFETY: The path was initialized by the syscall and is terminated
+        // by a null character.
+        let path = unsafe {
+            let length = (0..).take_while(|&i| *path.add(i) != 0).count();
+            slice::from_raw_parts(path, length)
+        };
+        Ok(OsString::from_wide(path).into())
+    } else {
+        Err(hresult_error(result))
+    };
+    // The buffer must be freed, even if the call failed.
+    unsafe {
+        CoTaskMemFree(path.cast());
+    }
+    result
 }
@@ -2,22 +2,32 @@ use std::borrow::Cow;
 use std::env;
 use std::ffi::OsStr;
 use std::ffi::OsString;
+use std::fs::OpenOptions;
 use std::io;
+use std::mem::MaybeUninit;
 use std::ops::Not;
 use std::os::windows::ffi::OsStrExt;
 use std::os::windows::ffi::OsStringExt;
+use std::os::windows::fs::MetadataExt;
+use std::os::windows::fs::OpenOptionsExt;
+use std::os::windows::io::AsRawHandle;
 use std::path::Component;
 use std::path::Path;
 use std::path::Prefix;
 use std::path::PrefixComponent;
 use std::ptr;
 
+use windows_sys::Win32::Storage::FileSystem::GetFileInformationByHandle;
 use windows_sys::Win32::Storage::FileSystem::GetFullPathNameW;
 use windows_sys::Win32::Storage::FileSystem::GetLongPathNameW;
 use windows_sys::Win32::Storage::FileSystem::GetShortPathNameW;
+use windows_sys::Win32::Storage::FileSystem::BY_HANDLE_FILE_INFORMATION;
+use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN;
+use windows_sys::Win32::Storage::FileSystem::FILE_FLAG_BACKUP_SEMANTICS;
 
 use crate::BasePath;
 use crate::BasePathBuf;
+use crate::FileId;
 
 #[cfg(feature = "localization")]
 pub(super) mod localize;
@@ -155,6 +165,47 @@ pub(crate) fn shorten(path: &Path) -> io::Result<Cow<'_, Path>> {
     })
 }
 
+fn file_information(path: &Path) -> io::Result<BY_HANDLE_FILE_INFORMATION> {
+    // Directories can only be opened using this flag, and no access is
+    // needed to read this information.
+    let file = OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)?;
+
+    let mut information = MaybeUninit::uninit();
+    // SAFETY: The handle is valid until the file is dropped, and the pointer
+    // is valid for writes.
+    let result = unsafe {
+        GetFileInformationByHandle(
+            file.as_raw_handle(),
+            information.as_mut_ptr(),
+        )
+    };
+    if result == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: The information was initialized by the successful call.
+    Ok(unsafe { information.assume_init() })
+}
+
+pub(crate) fn device_id(path: &Path) -> io::Result<u64> {
+    file_information(path).map(|x| x.dwVolumeSerialNumber.into())
+}
+
+pub(crate) fn file_id(path: &Path) -> io::Result<FileId> {
+    file_information(path).map(|x| {
+        let index =
+            u64::from(x.nFileIndexHigh) << 32 | u64::from(x.nFileIndexLow);
+        FileId::new(x.dwVolumeSerialNumber.into(), index)
+    })
+}
+
+pub(crate) fn is_hidden(path: &Path) -> io::Result<bool> {
+    path.symlink_metadata()
+        .map(|x| x.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+}
+
 fn get_prefix(base: &BasePath) -> PrefixComponent<'_> {
     if let Some(Component::Prefix(prefix)) = base.components().next() {
         prefix
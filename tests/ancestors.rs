@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use normpath::BasePath;
+
+fn ancestors(path: &str) -> Vec<Option<&Path>> {
+    BasePath::try_new(path)
+        .unwrap()
+        .ancestors()
+        .map(|x| x.ok().map(BasePath::as_path))
+        .collect()
+}
+
+fn ancestors_rev(path: &str) -> Vec<Option<&Path>> {
+    BasePath::try_new(path)
+        .unwrap()
+        .ancestors()
+        .rev()
+        .map(|x| x.ok().map(BasePath::as_path))
+        .collect()
+}
+
+#[track_caller]
+fn test(result: &[Option<&str>], path: &str) {
+    let result: Vec<_> = result.iter().map(|x| x.map(Path::new)).collect();
+    assert_eq!(result, ancestors(path));
+
+    let mut reversed = result;
+    reversed.reverse();
+    assert_eq!(reversed, ancestors_rev(path));
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_posix() {
+    test(&[Some("/foo/bar"), Some("/foo"), Some("/")], "/foo/bar");
+    test(&[Some("/foo/bar/"), Some("/foo"), Some("/")], "/foo/bar/");
+    test(&[Some("/")], "/");
+    test(&[Some("foo/bar"), Some("foo"), Some("")], "foo/bar");
+    test(&[Some("")], "");
+    test(&[Some("/foo/.."), None], "/foo/..");
+    test(&[Some("/foo/../bar"), Some("/foo/.."), None], "/foo/../bar");
+    test(&[Some("."), None], ".");
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows() {
+    test(
+        &[Some(r"X:\foo\bar"), Some(r"X:\foo"), Some(r"X:\")],
+        r"X:\foo\bar",
+    );
+    test(&[Some(r"X:\")], r"X:\");
+    test(&[Some(r"X:foo"), Some("X:")], r"X:foo");
+    test(
+        &[Some(r"\\server\share\foo"), Some(r"\\server\share\")],
+        r"\\server\share\foo",
+    );
+    test(&[Some(r"X:\foo\.."), None], r"X:\foo\..");
+    test(
+        &[Some(r"\\?\X:\foo\..\bar"), Some(r"\\?\X:\foo\.."), None],
+        r"\\?\X:\foo\..\bar",
+    );
+}
+
+#[test]
+fn test_mixed_direction() {
+    let path = if cfg!(windows) { r"X:\a\b\c" } else { "/a/b/c" };
+    let path = BasePath::try_new(path).unwrap();
+    let mut ancestors = path.ancestors();
+
+    assert_eq!(path, ancestors.next().unwrap().unwrap());
+    let root = ancestors.next_back().unwrap().unwrap();
+    assert_eq!(None, root.parent().unwrap());
+    assert_eq!(2, ancestors.clone().count());
+    assert_eq!(
+        Ok(path.parent().unwrap().unwrap()),
+        ancestors.next().unwrap()
+    );
+    assert!(ancestors.next().unwrap().is_ok());
+    assert!(ancestors.next().is_none());
+    assert!(ancestors.next_back().is_none());
+}
@@ -0,0 +1,159 @@
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use normpath::BasePathBuf;
+use normpath::PathExt;
+use tempfile::TempDir;
+
+mod common;
+use common::FakeEnv;
+
+fn create_tree() -> io::Result<(TempDir, BasePathBuf)> {
+    let dir = tempfile::tempdir()?;
+    let root = dir.path().normalize()?;
+
+    fs::create_dir_all(root.join("a/b/c"))?;
+    let _ = File::create(root.join("Cargo.toml"))?;
+    fs::create_dir(root.join("a/.git"))?;
+    let _ = File::create(root.join("a/b/Cargo.toml"))?;
+    Ok((dir, root))
+}
+
+fn matches<I>(iter: I) -> io::Result<Vec<BasePathBuf>>
+where
+    I: Iterator<Item = io::Result<BasePathBuf>>,
+{
+    iter.collect()
+}
+
+#[test]
+fn test_find() -> io::Result<()> {
+    let (_dir, root) = create_tree()?;
+    let start = root.join("a/b/c");
+
+    assert_eq!(
+        Some(root.join("a/b")),
+        start.find_ancestor_with(&["Cargo.toml"])?,
+    );
+    assert_eq!(
+        Some(root.join("a/b")),
+        start.find_ancestor_with(&[".git", "Cargo.toml"])?,
+    );
+    assert_eq!(Some(root.join("a")), start.find_ancestor_with(&[".git"])?);
+    assert_eq!(
+        Some(root.join("a/b")),
+        root.join("a/b").find_ancestor_with(&["Cargo.toml"])?,
+    );
+    assert_eq!(
+        Some(root.join("a/b")),
+        root.join("a/b/c/../../b/c")
+            .find_ancestor_with(&["Cargo.toml"])?,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_all() -> io::Result<()> {
+    let (_dir, root) = create_tree()?;
+    let start = root.join("a/b/c");
+
+    let result = matches(start.ancestors_with(&["Cargo.toml"]))?;
+    assert_eq!(root.join("a/b"), result[0]);
+    assert_eq!(root, result[1]);
+
+    let result = matches(start.ancestors_with(&["Cargo.toml", ".git"]))?;
+    assert_eq!(root.join("a/b"), result[0]);
+    assert_eq!(root.join("a"), result[1]);
+    assert_eq!(root, result[2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_stop_at() -> io::Result<()> {
+    let (_dir, root) = create_tree()?;
+    let start = root.join("a/b/c");
+
+    assert_eq!(
+        [root.join("a/b"), root.clone()],
+        *matches(start.ancestors_with(&["Cargo.toml"]).stop_at(&root))?,
+    );
+    assert_eq!(
+        [root.join("a/b")],
+        *matches(
+            start
+                .ancestors_with(&["Cargo.toml"])
+                .stop_at(root.join("a"))
+        )?,
+    );
+    assert_eq!(
+        [root.join("a/b")],
+        *matches(
+            start
+                .ancestors_with(&["Cargo.toml"])
+                .stop_at(root.join("a/b/c/.."))
+        )?,
+    );
+
+    let env = FakeEnv::default()
+        .var("HOME", root.join("a"))
+        .var("USERPROFILE", root.join("a"));
+    assert_eq!(
+        [root.join("a/b"), root.join("a")],
+        *matches(
+            start
+                .ancestors_with(&["Cargo.toml", ".git"])
+                .stop_at_home_with(&env)
+        )?,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stop_at_filesystem_boundary() -> io::Result<()> {
+    let (_dir, root) = create_tree()?;
+    let start = root.join("a/b/c");
+
+    let result = matches(
+        start
+            .ancestors_with(&["Cargo.toml"])
+            .stop_at_filesystem_boundary(),
+    )?;
+    assert_eq!(root.join("a/b"), result[0]);
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> io::Result<()> {
+    let (_dir, root) = create_tree()?;
+
+    let missing = root.join("missing");
+    let mut iter = missing.ancestors_with(&["Cargo.toml"]);
+    assert!(iter.next().expect("missing error").is_err());
+    assert!(iter.next().is_none());
+    assert!(missing.find_ancestor_with(&["x"]).is_err());
+
+    assert_eq!(None, root.join("a/b/c").find_ancestor_with::<&Path>(&[])?);
+
+    // Errors comparing file systems end iteration, since the boundary might
+    // otherwise be crossed. Markers are still checked first.
+    let _ = File::create(root.join("a/b/c/Cargo.toml"))?;
+    let start = root.join("a/b/c");
+    let mut iter = start
+        .ancestors_with(&["Cargo.toml", ".git"])
+        .stop_at_filesystem_boundary();
+    assert_eq!(start, iter.next().expect("missing match")?);
+    fs::remove_dir_all(root.join("a/b"))?;
+    assert_eq!(
+        io::ErrorKind::NotFound,
+        iter.next().expect("missing error").unwrap_err().kind(),
+    );
+    assert!(iter.next().is_none());
+
+    Ok(())
+}
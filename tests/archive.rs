@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use normpath::error::EntryError;
+use normpath::BasePath;
+use normpath::BasePathBuf;
+
+fn base() -> &'static BasePath {
+    let base = if cfg!(windows) { r"X:\out" } else { "/out" };
+    BasePath::try_new(base).unwrap()
+}
+
+#[track_caller]
+fn test(result: &[&str], name: &str) {
+    let mut path = PathBuf::from(base());
+    path.extend(result);
+    assert_eq!(
+        Ok(&path),
+        base()
+            .join_archive_entry(name)
+            .map(BasePathBuf::into_path_buf)
+            .as_ref(),
+    );
+    assert_eq!(
+        Ok(path),
+        base()
+            .join_archive_entry(name.as_bytes())
+            .map(BasePathBuf::into_path_buf),
+    );
+}
+
+#[track_caller]
+fn test_error(error: EntryError, name: &str) {
+    assert_eq!(Err(error), base().join_archive_entry(name));
+}
+
+#[test]
+fn test_valid() {
+    test(&["foo"], "foo");
+    test(&["foo", "bar"], "foo/bar");
+    test(&["foo", "bar"], r"foo\bar");
+    test(&["foo", "bar"], r"./foo//bar/");
+    test(&["bar"], r"foo/../bar");
+    test(&["foo", "baz"], r"foo\bar\.\..\baz");
+    test(&[], "");
+    test(&[], "./");
+    test(&[], "foo/..");
+    test(&["foo:bar"], "foo:bar");
+    test(&["1:"], "1:");
+    test(&["...", "..foo"], ".../..foo");
+}
+
+#[test]
+fn test_absolute() {
+    test_error(EntryError::Absolute, "/etc/passwd");
+    test_error(EntryError::Absolute, r"\Windows");
+    test_error(EntryError::Absolute, "/");
+}
+
+#[test]
+fn test_prefix() {
+    test_error(EntryError::DrivePrefix, r"C:\x");
+    test_error(EntryError::DrivePrefix, "c:x");
+    test_error(EntryError::DrivePrefix, "C:");
+    test_error(EntryError::DrivePrefix, "foo/C:/bar");
+    test_error(EntryError::UncPrefix, r"\\server\share\x");
+    test_error(EntryError::UncPrefix, "//server/share");
+    test_error(EntryError::UncPrefix, r"\\?\C:\x");
+    test_error(EntryError::UncPrefix, r"/\foo");
+}
+
+#[test]
+fn test_escapes_base() {
+    test_error(EntryError::EscapesBase, "..");
+    test_error(EntryError::EscapesBase, r"..\..\x");
+    test_error(EntryError::EscapesBase, r"a\..\..\b");
+    test_error(EntryError::EscapesBase, "a/./../../a/b");
+    test_error(EntryError::EscapesBase, "./../out/x");
+}
+
+#[test]
+fn test_invalid_encoding() {
+    let result = base().join_archive_entry(b"foo/\x80");
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        use std::path::Path;
+
+        assert_eq!(
+            Path::new("/out/foo").join(OsStr::from_bytes(b"\x80")),
+            result.unwrap(),
+        );
+    }
+    #[cfg(not(unix))]
+    assert_eq!(Err(EntryError::InvalidEncoding), result);
+}
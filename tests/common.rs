@@ -1,16 +1,57 @@
 #![allow(dead_code)]
 #![allow(unused_macros)]
 
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::io;
 use std::path::Path;
+use std::path::PathBuf;
 
 use normpath::BasePath;
 use normpath::BasePathBuf;
+use normpath::EnvProvider;
 use normpath::PathExt;
 
+#[derive(Clone, Default)]
+pub(crate) struct FakeEnv {
+    vars: HashMap<OsString, OsString>,
+    users: HashMap<OsString, PathBuf>,
+}
+
+impl FakeEnv {
+    pub(crate) fn var<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<OsString>,
+        V: Into<OsString>,
+    {
+        let _ = self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    pub(crate) fn user<U, P>(mut self, user: U, home: P) -> Self
+    where
+        U: Into<OsString>,
+        P: Into<PathBuf>,
+    {
+        let _ = self.users.insert(user.into(), home.into());
+        self
+    }
+}
+
+impl EnvProvider for FakeEnv {
+    fn var(&self, name: &OsStr) -> Option<OsString> {
+        self.vars.get(name).cloned()
+    }
+
+    fn user_home_dir(&self, user: &OsStr) -> Option<PathBuf> {
+        self.users.get(user).cloned()
+    }
+}
+
 // https://github.com/rust-lang/rust/issues/76483
 #[track_caller]
 pub(crate) fn assert_eq<P>(expected: &Path, result: io::Result<P>)
@@ -0,0 +1,118 @@
+use std::io;
+use std::path::Path;
+
+use normpath::error::ExpandError;
+use normpath::BasePathBuf;
+use normpath::ShellSyntax;
+
+mod common;
+
+use common::FakeEnv;
+
+fn env() -> FakeEnv {
+    let home = if cfg!(windows) {
+        r"X:\Users\foo"
+    } else {
+        "/home/foo"
+    };
+    FakeEnv::default()
+        .var(if cfg!(windows) { "USERPROFILE" } else { "HOME" }, home)
+        .var("XDG_DATA_HOME", "/data")
+        .var("APPDATA", r"X:\AppData")
+        .var("EMPTY", "")
+        .user(
+            "bar",
+            if cfg!(windows) {
+                r"X:\Users\bar"
+            } else {
+                "/home/bar"
+            },
+        )
+}
+
+#[track_caller]
+fn test(result: &str, path: &str, syntax: ShellSyntax) {
+    common::assert_eq(
+        Path::new(result),
+        BasePathBuf::expand_shell_as(path, syntax, &env()),
+    );
+}
+
+#[track_caller]
+fn test_error(error: ExpandError, path: &str, syntax: ShellSyntax) {
+    let result = BasePathBuf::expand_shell_as(path, syntax, &env())
+        .expect_err("expansion succeeded");
+    assert_eq!(
+        Some(&error),
+        result.get_ref().and_then(|x| x.downcast_ref()),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_posix() {
+    use ShellSyntax::Posix;
+
+    test("/home/foo", "~", Posix);
+    test("/home/foo/", "~/", Posix);
+    test("/home/foo/.cache", "~/.cache", Posix);
+    test("/home/bar/.cache", "~bar/.cache", Posix);
+    test("/foo/~/bar", "/foo/~/bar", Posix);
+
+    test("/data/app", "$XDG_DATA_HOME/app", Posix);
+    test("/data/app", "${XDG_DATA_HOME}/app", Posix);
+    test("/data_app", "${XDG_DATA_HOME}_app", Posix);
+    test("/foo/bar", "/foo$EMPTY/bar", Posix);
+    test("/foo/$/bar", "/foo/$/bar", Posix);
+    test("/foo/$1", "/foo/$1", Posix);
+    test("/foo/%APPDATA%", "/foo/%APPDATA%", Posix);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_windows_on_unix() {
+    use ShellSyntax::Windows;
+
+    test(r"/home/foo\bar", r"~\bar", Windows);
+    test(r"/home/bar\baz", r"~bar\baz", Windows);
+    test(r"X:\AppData\app", r"%APPDATA%\app", Windows);
+    test("/data/100%", "%XDG_DATA_HOME%/100%%", Windows);
+    test("/foo/100%/bar", "/foo/100%/bar", Windows);
+    test("/foo/$HOME", "/foo/$HOME", Windows);
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows() {
+    use ShellSyntax::Windows;
+
+    test(r"X:\Users\foo", "~", Windows);
+    test(r"X:\Users\foo\bar", r"~\bar", Windows);
+    test(r"X:\Users\bar\baz", "~bar/baz", Windows);
+    test(r"X:\AppData\app", r"%APPDATA%\app", Windows);
+    test(r"X:\AppData\100%", r"%APPDATA%\100%%", Windows);
+}
+
+#[test]
+fn test_errors() {
+    use ShellSyntax::Posix;
+    use ShellSyntax::Windows;
+
+    for syntax in [Posix, Windows] {
+        test_error(ExpandError::UnknownUser("baz".into()), "~baz", syntax);
+    }
+    test_error(ExpandError::UndefinedVariable("FOO".into()), "$FOO", Posix);
+    test_error(
+        ExpandError::UndefinedVariable("FOO".into()),
+        "%FOO%",
+        Windows,
+    );
+    test_error(ExpandError::InvalidSyntax, "${FOO", Posix);
+    test_error(ExpandError::InvalidSyntax, "${}", Posix);
+    test_error(ExpandError::InvalidSyntax, "${FOO-bar}", Posix);
+
+    let error = BasePathBuf::expand_shell("~", &FakeEnv::default())
+        .expect_err("expansion succeeded");
+    assert_eq!(io::ErrorKind::NotFound, error.kind());
+    assert_eq!("home directory is unknown", error.to_string());
+}
@@ -0,0 +1,102 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::BasePathBuf;
+
+fn root() -> &'static BasePath {
+    BasePath::try_new(if cfg!(windows) { r"X:\" } else { "/" }).unwrap()
+}
+
+fn extensions(name: &str) -> Vec<&OsStr> {
+    // The path is leaked to allow returning the borrowed extensions.
+    let path = Box::leak(root().join(name).into_boxed_base_path());
+    path.extensions().collect()
+}
+
+#[track_caller]
+fn test(prefix: Option<&str>, result: &[&str], name: &str) {
+    let result: Vec<_> = result.iter().map(OsStr::new).collect();
+    assert_eq!(result, extensions(name));
+
+    let path = root().join(name);
+    let mut reversed: Vec<_> = path.extensions().rev().collect();
+    reversed.reverse();
+    assert_eq!(result, reversed);
+
+    assert_eq!(prefix.map(OsStr::new), path.file_prefix());
+    assert_eq!(path.extension(), path.extensions().next());
+}
+
+#[test]
+fn test_extensions() {
+    test(Some("archive"), &["gz", "tar"], "archive.tar.gz");
+    test(Some("foo"), &["ts", "d"], "foo.d.ts");
+    test(Some("foo"), &[], "foo");
+    test(Some(".bashrc"), &[], ".bashrc");
+    test(Some(".config"), &["toml"], ".config.toml");
+    test(Some(".config"), &["gz", "tar"], "dir/.config.tar.gz");
+    test(Some("foo"), &["gz", ""], "foo..gz");
+    test(Some("foo"), &[""], "foo.");
+    test(Some("."), &["", ""], "...");
+    test(None, &[], "");
+    test(None, &[], "foo/..");
+}
+
+#[test]
+fn test_extensions_mixed() {
+    let path = root().join("a.b.c.d");
+    let mut extensions = path.extensions();
+    assert_eq!(Some(OsStr::new("d")), extensions.next());
+    assert_eq!(Some(OsStr::new("b")), extensions.next_back());
+    assert_eq!(Some(OsStr::new("c")), extensions.next());
+    assert_eq!(None, extensions.next_back());
+    assert_eq!(None, extensions.next());
+}
+
+#[test]
+fn test_has_extension_ci() {
+    let path = root().join("archive.Tar.GZ");
+    assert!(path.has_extension_ci("gz"));
+    assert!(path.has_extension_ci("GZ"));
+    assert!(path.has_extension_ci("tar.gz"));
+    assert!(!path.has_extension_ci("ar.gz"));
+    assert!(!path.has_extension_ci("z"));
+    assert!(!path.has_extension_ci(".gz"));
+    assert!(!path.has_extension_ci("archive.tar.gz"));
+    assert!(!path.has_extension_ci(""));
+
+    let path = root().join(".bashrc");
+    assert!(!path.has_extension_ci("bashrc"));
+    assert!(!root().has_extension_ci("gz"));
+}
+
+#[test]
+fn test_replace_extensions() {
+    #[track_caller]
+    fn test(result: &str, name: &str, count: usize, extension: &str) {
+        let mut path = root().join(name);
+        assert!(path.replace_extensions(count, extension));
+        assert_eq!(root().join(result), path);
+    }
+
+    test("foo.zip", "foo.tar.gz", 2, "zip");
+    test("foo.tar.zst", "foo.tar.gz", 1, "zst");
+    test("foo.tar.zst", "foo.tar.gz", 2, "tar.zst");
+    test("foo.zip", "foo.tar.gz", 5, "zip");
+    test("foo", "foo.tar.gz", 2, "");
+    test("foo.tar.gz", "foo.tar.gz", 0, "");
+    test("foo.tar.gz.bak", "foo.tar.gz", 0, "bak");
+    test(".bashrc.bak", ".bashrc", 1, "bak");
+    test(".config.json", ".config.toml", 1, "json");
+    test("foo..gz", "foo..gz", 1, "gz");
+    test("foo.gz", "foo..gz", 2, "gz");
+
+    let mut path = root().to_owned();
+    assert!(!path.replace_extensions(1, "gz"));
+    assert_eq!(root(), path);
+
+    let mut path = BasePathBuf::try_new(root().join("foo.rs/")).unwrap();
+    assert!(path.replace_extensions(1, "txt"));
+    assert_eq!(Path::new(&root().join("foo.txt")), path);
+}
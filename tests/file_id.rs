@@ -0,0 +1,71 @@
+#![cfg(any(unix, windows))]
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+
+use normpath::BasePathBuf;
+use normpath::PathExt;
+
+#[test]
+fn test_file_id() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().normalize()?;
+    let path = dir.join("foo");
+    let other = dir.join("bar");
+    fs::write(&path, "")?;
+    fs::write(&other, "")?;
+
+    assert_eq!(path.file_id()?, path.file_id()?);
+    assert_ne!(path.file_id()?, other.file_id()?);
+    fs::create_dir(dir.join("qux"))?;
+    assert_eq!(dir.file_id()?, dir.join("qux/..").file_id()?);
+
+    let link = dir.join("baz");
+    fs::hard_link(&path, &link)?;
+    assert!(path.same_file(&link)?);
+    assert!(!path.same_file(&other)?);
+
+    let ids: HashSet<_> = [&path, &other, &link]
+        .into_iter()
+        .map(|x| x.file_id())
+        .collect::<io::Result<_>>()?;
+    assert_eq!(2, ids.len());
+
+    assert_eq!(
+        io::ErrorKind::NotFound,
+        dir.join("missing").file_id().unwrap_err().kind(),
+    );
+    assert!(path.same_file(dir.join("missing")).is_err());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink() -> io::Result<()> {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().normalize()?;
+    let path = dir.join("foo");
+    fs::write(&path, "")?;
+    let link = dir.join("bar");
+    symlink(&path, &link)?;
+    assert!(link.same_file(&path)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_same_device() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().normalize()?;
+    let path = dir.join("foo");
+    fs::write(&path, "")?;
+    assert!(path.same_device(&dir)?);
+    assert!(BasePathBuf::new(&dir)?.same_device(dir.join("foo"))?);
+    assert!(path.same_device(dir.join("missing")).is_err());
+
+    Ok(())
+}
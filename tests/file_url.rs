@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use normpath::error::FileUrlError;
+use normpath::BasePath;
+use normpath::BasePathBuf;
+
+#[track_caller]
+fn test(url: &str, path: &str) {
+    assert_eq!(
+        Ok(url),
+        BasePath::try_new(path).unwrap().to_file_url().as_deref()
+    );
+    assert_eq!(
+        Path::new(path),
+        BasePathBuf::from_file_url(url).expect("invalid URL"),
+    );
+}
+
+#[track_caller]
+fn test_from(path: &str, url: &str) {
+    assert_eq!(
+        Path::new(path),
+        BasePathBuf::from_file_url(url).expect("invalid URL"),
+    );
+}
+
+#[track_caller]
+fn test_error(error: FileUrlError, url: &str) {
+    assert_eq!(Err(error), BasePathBuf::from_file_url(url));
+}
+
+#[test]
+fn test_errors() {
+    test_error(FileUrlError::InvalidScheme, "http://server/foo");
+    test_error(FileUrlError::InvalidScheme, "fil");
+    test_error(FileUrlError::InvalidPercentEncoding, "file:///foo%2");
+    test_error(FileUrlError::InvalidPercentEncoding, "file:///foo%zz");
+    test_error(FileUrlError::QueryOrFragment, "file:///foo?bar");
+    test_error(FileUrlError::QueryOrFragment, "file:///foo#bar");
+    test_error(FileUrlError::InvalidPath, "file:foo");
+    test_error(FileUrlError::InvalidPath, "file:///foo%2Fbar");
+    test_error(FileUrlError::InvalidPath, "file:///foo%00bar");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    test("file:///", "/");
+    test("file:///foo/bar", "/foo/bar");
+    test("file:///foo/bar/", "/foo/bar/");
+    test("file:///foo%20bar/%25%3F%23", "/foo bar/%?#");
+    test("file:///foo/C:/bar", "/foo/C:/bar");
+    test("file:///foo/%5Cbar", r"/foo/\bar");
+    test("file:///%C3%A9t%C3%A9", "/été");
+
+    test_from("/", "file://");
+    test_from("/foo", "file:/foo");
+    test_from("/foo", "FILE://localhost/foo");
+    test_from("/foo/été", "file:///foo/été");
+    test_from("/foo//bar", "file:///foo//bar");
+
+    let path = BasePath::try_new(OsStr::from_bytes(b"/f\x80o")).unwrap();
+    let url = path.to_file_url().unwrap();
+    assert_eq!("file:///f%80o", url);
+    assert_eq!(path, BasePathBuf::from_file_url(&url).unwrap());
+
+    test_error(FileUrlError::InvalidHost, "file://server/share/foo");
+
+    for path in ["foo", ""] {
+        assert_eq!(
+            Err(FileUrlError::UnsupportedPath),
+            BasePath::try_new(path).unwrap().to_file_url(),
+        );
+    }
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows() {
+    test("file:///C:/", r"C:\");
+    test("file:///C:/foo/bar", r"C:\foo\bar");
+    test("file:///C:/foo/bar/", r"C:\foo\bar\");
+    test("file:///C:/foo%20bar/%25", r"C:\foo bar\%");
+    test("file://server/share", r"\\server\share");
+    test("file://server/share/foo", r"\\server\share\foo");
+
+    test_from(r"C:\", "file:///C:");
+    test_from(r"C:\foo", "file:///C|/foo");
+    test_from(r"C:\foo", "file://localhost/C:/foo");
+    test_from(r"\\server\share\foo", "file:////server/share/foo");
+
+    #[track_caller]
+    fn test_to(url: &str, path: &str) {
+        assert_eq!(
+            Ok(url),
+            BasePath::try_new(path).unwrap().to_file_url().as_deref(),
+        );
+    }
+
+    test_to("file:///C:/foo/bar", r"C:/foo/bar");
+    test_to("file:///C:/foo", r"\\?\C:\foo");
+    test_to("file://server/share/foo", r"\\?\UNC\server\share\foo");
+
+    for path in [r"C:foo", r"\\.\COM1", r"\\?\foo\bar"] {
+        assert_eq!(
+            Err(FileUrlError::UnsupportedPath),
+            BasePath::try_new(path).unwrap().to_file_url(),
+        );
+    }
+
+    test_error(FileUrlError::InvalidPath, "file:///foo");
+    test_error(FileUrlError::InvalidPath, "file:///C:/foo%5Cbar");
+    test_error(FileUrlError::InvalidPath, "file://server");
+    test_error(FileUrlError::InvalidPath, "file:///C:/%80");
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::io;
+
+use normpath::BasePath;
+use normpath::PathExt;
+
+#[test]
+fn test_missing() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        io::ErrorKind::NotFound,
+        dir.path().join("foo").is_hidden().unwrap_err().kind(),
+    );
+}
+
+#[test]
+fn test_visible() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("foo");
+    fs::write(&path, "")?;
+    assert!(!path.is_hidden()?);
+
+    // Names of temporary directories start with a `.` character.
+    let path = dir.path().join("bar");
+    fs::create_dir(&path)?;
+    assert!(!BasePath::try_new(&path).unwrap().is_hidden()?);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    for name in [".foo", "bar", "baz"] {
+        fs::write(dir.path().join(name), "")?;
+    }
+    fs::create_dir(dir.path().join(".qux"))?;
+
+    assert!(dir.path().join(".foo").is_hidden()?);
+    assert!(dir.path().join(".qux").is_hidden()?);
+    assert!(dir.path().join(".qux/.").is_hidden()?);
+    assert!(!dir.path().join(".qux/..").is_hidden()?);
+
+    fs::write(dir.path().join(".hidden"), "bar\nqux\n")?;
+    let is_hidden = dir.path().join("bar").is_hidden()?;
+    assert_eq!(
+        !cfg!(any(target_os = "ios", target_os = "macos")),
+        is_hidden,
+    );
+    assert!(!dir.path().join("baz").is_hidden()?);
+
+    Ok(())
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows() -> io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+
+    use windows_sys::Win32::Storage::FileSystem::SetFileAttributesW;
+    use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join(".foo");
+    fs::write(&path, "")?;
+    assert!(!path.is_hidden()?);
+
+    let mut wide_path: Vec<_> = path.as_os_str().encode_wide().collect();
+    wide_path.push(0);
+    let result = unsafe {
+        SetFileAttributesW(wide_path.as_ptr(), FILE_ATTRIBUTE_HIDDEN)
+    };
+    if result == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    assert!(path.is_hidden()?);
+
+    Ok(())
+}
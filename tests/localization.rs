@@ -4,6 +4,8 @@ use std::path::Path;
 
 use normpath::PathExt;
 
+mod common;
+
 #[track_caller]
 fn test(result: &str, path: &str) {
     assert_eq!(result, &*Path::new(path).localize_name());
@@ -45,6 +47,44 @@ fn test_windows_parent() {
     let _ = Path::new(r"X:\foo\bar\..").localize_name();
 }
 
+#[test]
+fn test_locale() {
+    for locale in ["de_DE", "C", ""] {
+        let path = Path::new("/normpath/foo/test.rs");
+        assert_eq!("test.rs", &*path.localize_name_in(locale));
+        assert_eq!("", &*Path::new("").localize_name_in(locale));
+    }
+}
+
+#[test]
+fn test_try() {
+    use normpath::error::LocalizeError;
+
+    assert_eq!(Err(LocalizeError::Empty), Path::new("").try_localize_name());
+    assert_eq!(
+        Err(LocalizeError::ParentComponent),
+        Path::new("/foo/bar/..").try_localize_name(),
+    );
+    if cfg!(windows) {
+        assert_eq!(
+            Err(LocalizeError::ParentComponent),
+            Path::new(r"X:\foo\bar\..").try_localize_name(),
+        );
+        assert_eq!(
+            Err(LocalizeError::InvalidPath),
+            Path::new("/foo/bar/test\0.rs").try_localize_name(),
+        );
+    }
+
+    if cfg!(all(unix, not(any(target_os = "ios", target_os = "macos")))) {
+        let name = Path::new("/normpath/foo/test.rs")
+            .try_localize_name()
+            .unwrap();
+        assert_eq!("test.rs", name.name());
+        assert!(!name.is_localized());
+    }
+}
+
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 #[test]
 fn test_localized() {
@@ -72,3 +112,104 @@ fn test_invalid() {
         assert_eq!(path, Path::new(path).localize_name());
     }
 }
+
+#[cfg(all(unix, not(any(target_os = "ios", target_os = "macos"))))]
+#[test]
+fn test_known_folder_xdg() -> std::io::Result<()> {
+    use std::fs;
+
+    use normpath::KnownFolder;
+
+    use common::FakeEnv;
+
+    #[track_caller]
+    fn test(result: &str, folder: KnownFolder, env: &FakeEnv) {
+        common::assert_eq(
+            Path::new(result),
+            normpath::known_folder_with(folder, env),
+        );
+    }
+
+    let env = FakeEnv::default().var("HOME", "/home/foo");
+    test("/home/foo", KnownFolder::Home, &env);
+    test("/home/foo/.cache", KnownFolder::Cache, &env);
+    test("/home/foo/.config", KnownFolder::Config, &env);
+    test("/home/foo/.local/share", KnownFolder::Data, &env);
+    test("/home/foo/Desktop", KnownFolder::Desktop, &env);
+    assert_eq!(
+        std::io::ErrorKind::NotFound,
+        normpath::known_folder_with(KnownFolder::Documents, &env)
+            .unwrap_err()
+            .kind(),
+    );
+
+    let config = tempfile::tempdir()?;
+    fs::write(
+        config.path().join("user-dirs.dirs"),
+        concat!(
+            "# This file is written by xdg-user-dirs-update\n",
+            "XDG_DESKTOP_DIR=\"$HOME/Schreibtisch\"\n",
+            "XDG_DOCUMENTS_DIR=\"$HOME/Dokumente\"\n",
+            "XDG_DOWNLOAD_DIR=\"/srv/\\\"downloads\\\"\"\n",
+            "XDG_MUSIC_DIR=\"$HOME\"\n",
+            "XDG_PICTURES_DIR=\"relative\"\n",
+            "XDG_VIDEOS_DIR=\"$HOMEVideos\"\n",
+        ),
+    )?;
+    let env = env
+        .var("XDG_CONFIG_HOME", config.path())
+        .var("XDG_CACHE_HOME", "relative")
+        .var("XDG_DATA_HOME", "/data");
+    test("/home/foo/.cache", KnownFolder::Cache, &env);
+    test("/data", KnownFolder::Data, &env);
+    test("/home/foo/Schreibtisch", KnownFolder::Desktop, &env);
+    test("/home/foo/Dokumente", KnownFolder::Documents, &env);
+    test(r#"/srv/"downloads""#, KnownFolder::Downloads, &env);
+    test("/home/foo", KnownFolder::Music, &env);
+    for folder in [KnownFolder::Pictures, KnownFolder::Videos] {
+        assert!(normpath::known_folder_with(folder, &env).is_err());
+    }
+
+    assert!(normpath::known_folder_with(
+        KnownFolder::Home,
+        &FakeEnv::default()
+    )
+    .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_components() {
+    use std::borrow::Cow;
+    use std::ffi::OsStr;
+    use std::path::Component;
+
+    use normpath::BasePath;
+
+    let path = if cfg!(windows) {
+        r"X:\normpath\foo\..\bar"
+    } else {
+        "/normpath/foo/../bar"
+    };
+    let path = BasePath::try_new(path).unwrap();
+
+    let components: Vec<_> = path.localize_components().collect();
+    assert_eq!(path.components().count(), components.len());
+    for ((component, name), expected) in
+        components.iter().zip(path.components())
+    {
+        assert_eq!(expected, *component);
+        if !matches!(component, Component::Normal(_)) {
+            assert_eq!(Cow::Borrowed(component.as_os_str()), *name);
+        }
+    }
+    assert_eq!(
+        Some(&(Component::ParentDir, Cow::Borrowed(OsStr::new("..")))),
+        components.get(components.len() - 2),
+    );
+
+    if !cfg!(any(target_os = "ios", target_os = "macos")) {
+        assert_eq!(path.as_os_str(), path.localize_path());
+    }
+}
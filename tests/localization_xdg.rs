@@ -0,0 +1,449 @@
+#![cfg(all(
+    feature = "localization",
+    unix,
+    not(any(target_os = "ios", target_os = "macos")),
+))]
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use normpath::PathExt;
+use tempfile::TempDir;
+
+mod common;
+use common::FakeEnv;
+
+// Writes a message catalog in the GNU gettext format.
+fn write_catalog(path: &Path, messages: &[(&str, &str)]) -> io::Result<()> {
+    let count = u32::try_from(messages.len()).unwrap();
+    let originals = 28;
+    let translations = originals + 8 * count;
+    let mut strings = translations + 8 * count;
+
+    let mut header = Vec::new();
+    let mut tables = [Vec::new(), Vec::new()];
+    let mut data = Vec::new();
+    for value in [0x9504_12DE, 0, count, originals, translations, 0, 0] {
+        header.extend(u32::to_le_bytes(value));
+    }
+    for (i, table) in tables.iter_mut().enumerate() {
+        for message in messages {
+            let message = if i == 0 { message.0 } else { message.1 };
+            let len = u32::try_from(message.len()).unwrap();
+            table.extend(len.to_le_bytes());
+            table.extend(strings.to_le_bytes());
+            data.extend(message.as_bytes());
+            data.push(0);
+            strings += len + 1;
+        }
+    }
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, [header, tables.concat(), data].concat())
+}
+
+struct Fixture {
+    _dir: TempDir,
+    home: PathBuf,
+    env: FakeEnv,
+}
+
+impl Fixture {
+    fn new(user_dirs_locale: &str) -> io::Result<Self> {
+        let dir = tempfile::tempdir()?;
+        let home = dir.path().join("home");
+        let config = dir.path().join("config");
+        let data = dir.path().join("data");
+
+        fs::create_dir_all(&config)?;
+        fs::write(
+            config.join("user-dirs.dirs"),
+            concat!(
+                "XDG_DESKTOP_DIR=\"$HOME/Desktop\"\n",
+                "XDG_DOCUMENTS_DIR=\"$HOME/Documents\"\n",
+                "XDG_MUSIC_DIR=\"$HOME/Songs\"\n",
+                "XDG_PICTURES_DIR=\"$HOME/Bilder\"\n",
+            ),
+        )?;
+        fs::write(
+            config.join("user-dirs.locale"),
+            format!("{}\n", user_dirs_locale),
+        )?;
+
+        let messages = [
+            ("Documents", "Dokumente"),
+            ("Music", "Musik"),
+            ("Pictures", "Bilder"),
+        ];
+        write_catalog(
+            &data.join("locale/de/LC_MESSAGES/xdg-user-dirs.mo"),
+            &messages,
+        )?;
+        write_catalog(
+            &data.join("locale/de_AT/LC_MESSAGES/xdg-user-dirs.mo"),
+            &[("Documents", "Dokumente (AT)")],
+        )?;
+
+        let env = FakeEnv::default()
+            .var("HOME", &home)
+            .var("XDG_CONFIG_HOME", &config)
+            .var("XDG_DATA_DIRS", format!("relative:{}", data.display()));
+        Ok(Self {
+            _dir: dir,
+            home,
+            env,
+        })
+    }
+
+    #[track_caller]
+    fn test(&self, result: &str, name: &str, locale: &str) {
+        let env = self.env.clone().var("LANG", locale);
+        assert_eq!(result, &*self.home.join(name).localize_name_with(&env));
+    }
+}
+
+#[test]
+fn test_user_dirs() -> io::Result<()> {
+    let fixture = Fixture::new("en_US")?;
+    fixture.test("Dokumente", "Documents", "de_DE.UTF-8");
+    fixture.test("Dokumente (AT)", "Documents", "de_AT.UTF-8");
+    fixture.test("Documents", "Documents", "C");
+    fixture.test("Documents", "Documents", "fr_FR.UTF-8");
+    fixture.test("Desktop", "Desktop", "de_DE.UTF-8");
+
+    // Renamed directories are not translated.
+    fixture.test("Songs", "Songs", "de_DE.UTF-8");
+    fixture.test("Bilder", "Bilder", "C");
+
+    fixture.test("Other", "Other", "de_DE.UTF-8");
+
+    let env = fixture.env.clone().var("LANG", "de_DE.UTF-8");
+    let path = fixture.home.join("Documents");
+    let name = path.try_localize_name_with(&env).unwrap();
+    assert_eq!("Dokumente", name.name());
+    assert!(name.is_localized());
+    for name in ["Songs", "Other"] {
+        let path = fixture.home.join(name);
+        let localized = path.try_localize_name_with(&env).unwrap();
+        assert!(!localized.is_localized());
+        assert_eq!(name, &*localized.into_name());
+    }
+
+    let fixture = Fixture::new("de_DE")?;
+    fixture.test("Pictures", "Bilder", "C");
+    fixture.test("Pictures", "Bilder", "en_US.UTF-8");
+    fixture.test("Bilder", "Bilder", "de_DE.UTF-8");
+    fixture.test("Documents", "Documents", "C");
+
+    Ok(())
+}
+
+#[test]
+fn test_locale_variables() -> io::Result<()> {
+    let fixture = Fixture::new("C")?;
+    let path = fixture.home.join("Documents");
+
+    let env = fixture
+        .env
+        .clone()
+        .var("LANG", "C")
+        .var("LC_MESSAGES", "de");
+    assert_eq!("Dokumente", &*path.localize_name_with(&env));
+
+    let env = env.var("LC_ALL", "fr_FR");
+    assert_eq!("Documents", &*path.localize_name_with(&env));
+
+    let env = env.var("LC_ALL", "");
+    assert_eq!("Dokumente", &*path.localize_name_with(&env));
+
+    Ok(())
+}
+
+#[test]
+fn test_directory_file() -> io::Result<()> {
+    let fixture = Fixture::new("en_US")?;
+    for name in ["Projects", "Documents"] {
+        let path = fixture.home.join(name);
+        fs::create_dir_all(&path)?;
+        fs::write(
+            path.join(".directory"),
+            concat!(
+                "[Desktop Entry]\n",
+                "Icon=folder-development\n",
+                "Name=Projects\n",
+                "Name[de]=Projekte\n",
+                "Name[de_CH] = Projekt\\sOrdner\n",
+                "\n",
+                "[Other]\n",
+                "Name[fr]=Projets\n",
+            ),
+        )?;
+    }
+
+    fixture.test("Projekte", "Projects", "de_DE.UTF-8");
+    fixture.test("Projekt Ordner", "Projects", "de_CH.UTF-8@euro");
+    fixture.test("Projects", "Projects", "fr_FR.UTF-8");
+    fixture.test("Projects", "Projects", "C");
+    fixture.test("Projekte", "Documents", "de_DE.UTF-8");
+
+    let path = fixture.home.join("Icons");
+    fs::create_dir_all(&path)?;
+    fs::write(path.join(".directory"), "[Desktop Entry]\nIcon=folder\n")?;
+    fixture.test("Icons", "Icons", "de_DE.UTF-8");
+
+    Ok(())
+}
+
+#[test]
+fn test_path() -> io::Result<()> {
+    use normpath::BasePath;
+
+    let fixture = Fixture::new("en_US")?;
+    let path = fixture.home.join("Documents/Projects");
+    fs::create_dir_all(&path)?;
+    fs::write(
+        path.join(".directory"),
+        "[Desktop Entry]\nName[de]=Projekte\n",
+    )?;
+
+    let env = fixture.env.clone().var("LANG", "de_DE.UTF-8");
+    let path = BasePath::try_new(&path).unwrap().join("../Projects/src");
+    let names: Vec<_> = path
+        .localize_components_with(&env)
+        .map(|(_, x)| x.into_owned())
+        .collect();
+    assert_eq!(
+        ["Dokumente", "Projekte", "..", "Projekte", "src"],
+        names[names.len() - 5..],
+    );
+
+    assert_eq!(
+        fixture.home.join("Dokumente/Projekte/../Projekte/src"),
+        Path::new(&path.localize_path_with(&env)),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_locale() -> io::Result<()> {
+    let fixture = Fixture::new("en_US")?;
+    let path = fixture.home.join("Documents");
+    let env = fixture.env.clone().var("LANG", "fr_FR.UTF-8");
+    assert_eq!("Dokumente", &*path.localize_name_in_with("de_DE", &env));
+    assert_eq!(
+        "Dokumente (AT)",
+        &*path.localize_name_in_with("de-AT", &env),
+    );
+    assert_eq!("Documents", &*path.localize_name_in_with("C", &env));
+    assert_eq!("Documents", &*path.localize_name_in_with("", &env));
+
+    let env = env.var("LC_ALL", "de_DE.UTF-8");
+    assert_eq!("Documents", &*path.localize_name_in_with("fr_FR", &env));
+
+    Ok(())
+}
+
+#[test]
+fn test_bundle() -> io::Result<()> {
+    let fixture = Fixture::new("en_US")?;
+    let path = fixture.home.join("Projects.localized");
+    let bundle = path.join(".localized");
+    fs::create_dir_all(&bundle)?;
+    fs::write(
+        bundle.join("de.strings"),
+        concat!(
+            "/* Localized name of the directory. */\n",
+            "\"Projects\" = \"Projekte\";\n",
+            "// Other entries are ignored.\n",
+            "Other = Andere;\n",
+        ),
+    )?;
+    let strings: Vec<_> = "\u{FEFF}\"Projects\" = \"Projets\";"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    fs::write(bundle.join("fr.strings"), strings)?;
+    fs::write(bundle.join("es.strings"), "\"Projects\" = \"Proyectos\"")?;
+
+    let env = &fixture.env;
+    for (result, locale) in [
+        ("Projekte", "de"),
+        ("Projekte", "de_CH.UTF-8"),
+        ("Projets", "fr_FR"),
+        ("Projects.localized", "es_ES"),
+        ("Projects.localized", "it"),
+        ("Projects.localized", "C"),
+    ] {
+        assert_eq!(result, &*path.localize_name_in_with(locale, env));
+    }
+
+    let env = env.clone().var("LANG", "de_DE.UTF-8");
+    assert_eq!("Projekte", &*path.localize_name_with(&env));
+
+    let path = fixture.home.join("Other.localized");
+    fs::create_dir_all(path.join(".localized"))?;
+    fs::write(
+        path.join(".localized/de.strings"),
+        "\"Other\" = \"\\\"Andere\\\" \\U00C4\";",
+    )?;
+    assert_eq!(
+        "\"Andere\" \u{C4}",
+        &*path.localize_name_in_with("de", &env),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resolve() -> io::Result<()> {
+    use normpath::BasePath;
+    use normpath::LocalizedNameCache;
+
+    let fixture = Fixture::new("en_US")?;
+    for name in ["Documents", "Other", "Projects"] {
+        fs::create_dir_all(fixture.home.join(name))?;
+    }
+    fs::write(
+        fixture.home.join("Projects/.directory"),
+        "[Desktop Entry]\nName[de]=Projekte\n",
+    )?;
+
+    let env = fixture.env.clone().var("LANG", "de_DE.UTF-8");
+    let home = BasePath::try_new(&fixture.home).unwrap();
+    let mut cache = LocalizedNameCache::with_env(&env);
+    for (result, name) in [
+        (Some("Documents"), "Dokumente"),
+        (Some("Projects"), "Projekte"),
+        (Some("Other"), "Other"),
+        (None, "Documents"),
+        (None, "dokumente"),
+        (None, "Missing"),
+    ] {
+        let result = result.map(|x| fixture.home.join(x));
+        assert_eq!(
+            result,
+            home.resolve_localized_child_with(name, &env)?
+                .map(|x| x.into_path_buf()),
+        );
+        assert_eq!(
+            result,
+            cache.resolve(home, name)?.map(|x| x.into_path_buf()),
+        );
+    }
+
+    fs::create_dir(fixture.home.join("Missing"))?;
+    assert_eq!(None, cache.resolve(home, "Missing")?);
+    cache.clear();
+    assert_eq!(
+        Some(fixture.home.join("Missing")),
+        cache.resolve(home, "Missing")?.map(|x| x.into_path_buf()),
+    );
+
+    assert!(home
+        .join("Missing/foo")
+        .resolve_localized_child("foo")
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_type_name() -> io::Result<()> {
+    let fixture = Fixture::new("en_US")?;
+    let mime = fixture.home.join("../data/mime");
+    fs::create_dir_all(mime.join("inode"))?;
+    fs::create_dir_all(mime.join("text"))?;
+    fs::create_dir_all(mime.join("application"))?;
+    fs::write(
+        mime.join("globs2"),
+        concat!(
+            "# This file was automatically generated.\n",
+            "50:text/plain:*.txt\n",
+            "50:text/x-readme:README*:cs\n",
+            "80:text/x-readme:README.txt:cs\n",
+            "60:application/x-archive:*.tar.[gx]z\n",
+        ),
+    )?;
+    fs::write(
+        mime.join("magic"),
+        [
+            &b"MIME-Magic\0\n"[..],
+            b"[60:application/x-archive]\n",
+            b">0=\0\x03ARC\n",
+            b"1>3=\0\x01A\n",
+            b"1>3=\0\x01B\n",
+            b"[50:text/plain]\n",
+            b">0=\0\x02tx&\xDF\xDF+2\n",
+        ]
+        .concat(),
+    )?;
+    fs::write(
+        mime.join("text/plain.xml"),
+        concat!(
+            "<?xml version=\"1.0\"?>\n",
+            "<mime-type xmlns=\"http://www.freedesktop.org/standards/shared-mime-info\" type=\"text/plain\">\n",
+            "  <comment>plain text document</comment>\n",
+            "  <comment xml:lang=\"de\">Einfaches Textdokument</comment>\n",
+            "  <comment xml:lang=\"de_AT\">Text &amp; &#x44;okument</comment>\n",
+            "</mime-type>\n",
+        ),
+    )?;
+    fs::write(
+        mime.join("inode/directory.xml"),
+        "<mime-type type=\"inode/directory\"><comment>folder</comment>\
+         <comment xml:lang=\"de\">Ordner</comment></mime-type>",
+    )?;
+    fs::write(
+        mime.join("application/x-archive.xml"),
+        "<mime-type><comment>archive</comment></mime-type>",
+    )?;
+
+    let dir = fixture.home.join("files");
+    fs::create_dir_all(&dir)?;
+    for (name, contents) in [
+        ("archive", "ARCB"),
+        ("invalid", "ARCC"),
+        ("text", "\0TX"),
+        ("README", ""),
+        ("README.txt", ""),
+    ] {
+        fs::write(dir.join(name), contents)?;
+    }
+
+    #[track_caller]
+    fn test(result: Option<&str>, path: &Path, env: &FakeEnv) {
+        assert_eq!(
+            result,
+            path.localize_type_name_with(env)
+                .as_deref()
+                .map(|x| x.to_str().unwrap()),
+        );
+    }
+
+    let env = fixture.env.clone().var("LANG", "de_DE.UTF-8");
+    test(Some("Einfaches Textdokument"), &dir.join("foo.TXT"), &env);
+    test(Some("Ordner"), &dir, &env);
+    test(Some("archive"), &dir.join("foo.tar.gz"), &env);
+    test(None, &dir.join("foo.tar.bz"), &env);
+    test(Some("archive"), &dir.join("archive"), &env);
+    test(None, &dir.join("invalid"), &env);
+    test(Some("Einfaches Textdokument"), &dir.join("text"), &env);
+    test(None, &dir.join("README"), &env);
+    test(None, &dir.join("README.txt"), &env);
+    test(
+        Some("Einfaches Textdokument"),
+        &dir.join("readme.txt"),
+        &env,
+    );
+
+    let env = fixture.env.clone().var("LANG", "de_AT.UTF-8");
+    test(Some("Text & Dokument"), &dir.join("foo.txt"), &env);
+    let env = env.var("LANG", "C");
+    test(Some("plain text document"), &dir.join("foo.txt"), &env);
+    test(Some("folder"), &dir, &env);
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::env;
+use std::io;
+use std::path::Path;
+
+use normpath::BasePathBuf;
+use normpath::LogicalPathBuf;
+use normpath::PathExt;
+
+mod common;
+use common::FakeEnv;
+
+#[test]
+fn test_push() {
+    let base = if cfg!(windows) { r"X:\" } else { "/" };
+    let base = Path::new(base);
+    let path = BasePathBuf::try_new(base.join("foo/./bar/../baz")).unwrap();
+
+    let mut path = LogicalPathBuf::new(path);
+    assert_eq!(base.join("foo/baz"), path.as_path());
+
+    path.push("link/..");
+    assert_eq!(base.join("foo/baz"), path.as_path());
+
+    path.push("../../..");
+    assert_eq!(base, path.as_path());
+
+    path.push("qux/.");
+    assert_eq!(base.join("qux"), path.as_path());
+
+    path.push(base.join("foo/../bar"));
+    assert_eq!(base.join("bar"), path.as_path());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_push_relative() {
+    let path = BasePathBuf::try_new("foo/../..").unwrap();
+    let mut path = LogicalPathBuf::new(path);
+    assert_eq!(Path::new(".."), path.as_path());
+
+    path.push("../bar/..");
+    assert_eq!(Path::new("../.."), path.as_path());
+}
+
+#[cfg(windows)]
+#[test]
+fn test_push_drive_relative() {
+    let path = BasePathBuf::try_new("X:foo/../..").unwrap();
+    let mut path = LogicalPathBuf::new(path);
+    assert_eq!(Path::new("X:.."), path.as_path());
+
+    path.push(r"..\bar");
+    assert_eq!(Path::new(r"X:..\..\bar"), path.as_path());
+
+    path.push(r"X:\foo\..\..");
+    assert_eq!(Path::new(r"X:\"), path.as_path());
+}
+
+#[test]
+fn test_current_dir() -> io::Result<()> {
+    let current_dir = env::current_dir()?;
+    let physical = BasePathBuf::new(&current_dir)?;
+    let assert_physical = |env: &FakeEnv| -> io::Result<()> {
+        assert_eq!(physical, BasePathBuf::logical_current_dir_with(env)?);
+        Ok(())
+    };
+
+    assert_physical(&FakeEnv::default())?;
+    assert_physical(&FakeEnv::default().var("PWD", "foo"))?;
+
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().normalize()?;
+    assert_physical(&FakeEnv::default().var("PWD", dir.as_os_str()))?;
+
+    let mut pwd = current_dir.into_os_string();
+    pwd.push("/.");
+    let env = FakeEnv::default().var("PWD", pwd);
+    assert_eq!(physical, BasePathBuf::logical_current_dir_with(&env)?);
+
+    assert!(BasePathBuf::logical_current_dir()?.same_file(&physical)?);
+    assert_eq!(
+        physical,
+        LogicalPathBuf::current_dir_with(&FakeEnv::default())?
+            .into_base_path_buf(),
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_current_dir_link() -> io::Result<()> {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().normalize()?;
+    let link = dir.join("link");
+    symlink(env::current_dir()?, &link)?;
+
+    let env = FakeEnv::default().var("PWD", link.as_os_str());
+    assert_eq!(link, BasePathBuf::logical_current_dir_with(&env)?);
+
+    let mut path = LogicalPathBuf::current_dir_with(&env)?;
+    assert_eq!(link.as_path(), path.as_path());
+    path.push("..");
+    assert_eq!(dir.as_path(), path.as_path());
+    assert_ne!(dir, link.join("..").normalize()?);
+
+    let env = FakeEnv::default().var("PWD", link.join("foo/..").as_os_str());
+    assert_eq!(
+        env::current_dir()?,
+        BasePathBuf::logical_current_dir_with(&env)?,
+    );
+    Ok(())
+}
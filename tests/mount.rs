@@ -0,0 +1,82 @@
+#![cfg(target_os = "linux")]
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::PathExt;
+
+#[test]
+fn test_root() -> io::Result<()> {
+    let info = BasePath::new(Path::new("/"))?.mount_info()?;
+    assert_eq!(Path::new("/"), info.mount_point());
+    assert!(!info.fs_type().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mount_point = dir.path().normalize()?.join("foo bar");
+    let path = mount_point.join("baz");
+    fs::create_dir_all(&path)?;
+
+    let escaped = mount_point
+        .as_os_str()
+        .to_str()
+        .unwrap()
+        .replace(' ', "\\040");
+    let mountinfo = dir.path().join("mountinfo");
+    fs::write(
+        &mountinfo,
+        format!(
+            concat!(
+                "21 1 0:20 / / rw,relatime shared:1 - ext4 /dev/sda1 rw\n",
+                "22 21 0:21 / {0} rw shared:2 - tmpfs tmpfs rw,size=8k\n",
+                "23 21 0:22 /export {0} ro,nosuid - nfs4 server:/export rw\n",
+                "24 21 0:23 / {0}/baz/qux rw - tmpfs tmpfs rw\n",
+                "25 21 0:24 / {0}baz rw - tmpfs tmpfs rw\n",
+            ),
+            escaped,
+        ),
+    )?;
+
+    let info = path.mount_info_from(&mountinfo)?;
+    assert_eq!(23, info.id());
+    assert_eq!(21, info.parent_id());
+    assert_eq!((0, 22), info.device());
+    assert_eq!(Path::new("/export"), info.root());
+    assert_eq!(mount_point, info.mount_point());
+    assert_eq!(["ro", "nosuid"], info.options());
+    assert_eq!("nfs4", info.fs_type());
+    assert_eq!("server:/export", info.source());
+    assert_eq!(["rw"], info.super_options());
+
+    let info = dir.path().normalize()?.mount_info_from(&mountinfo)?;
+    assert_eq!(21, info.id());
+    assert_eq!(Path::new("/"), info.mount_point());
+
+    assert_eq!(
+        io::ErrorKind::NotFound,
+        path.join("missing")
+            .mount_info_from(&mountinfo)
+            .unwrap_err()
+            .kind(),
+    );
+
+    fs::write(&mountinfo, "22 21 0:21 / /foo rw - tmpfs\n")?;
+    assert_eq!(
+        io::ErrorKind::InvalidData,
+        path.mount_info_from(&mountinfo).unwrap_err().kind(),
+    );
+
+    fs::write(&mountinfo, "22 21 0:21 / /foo rw - tmpfs tmpfs rw\n")?;
+    assert_eq!(
+        io::ErrorKind::NotFound,
+        path.mount_info_from(&mountinfo).unwrap_err().kind(),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::BasePathBuf;
+
+fn base(path: &str) -> BasePathBuf {
+    BasePathBuf::try_new(path).unwrap()
+}
+
+macro_rules! root {
+    ( $path:expr ) => {
+        if cfg!(windows) {
+            concat!(r"X:\", $path)
+        } else {
+            concat!("/", $path)
+        }
+    };
+}
+
+#[test]
+fn test_set_file_name() {
+    let mut path = base(root!("foo/bar.rs"));
+    path.set_file_name("baz.txt").unwrap();
+    assert_eq!(Path::new(root!("foo")).join("baz.txt"), path);
+
+    let mut path = base(root!(""));
+    path.set_file_name("foo").unwrap();
+    assert_eq!(Path::new(root!("foo")), path);
+
+    let mut path = base(root!("foo/bar.rs"));
+    for name in ["", ".", "..", "a/b", "/a", "a/"] {
+        assert!(path.set_file_name(name).is_err(), "{:?}", name);
+    }
+    if cfg!(windows) {
+        for name in [r"a\b", r"Y:\a", "Y:", r"\\server\share"] {
+            assert!(path.set_file_name(name).is_err(), "{:?}", name);
+        }
+    }
+    assert_eq!(Path::new(root!("foo/bar.rs")), path);
+
+    assert_eq!(
+        Path::new(root!("foo")).join("baz"),
+        BasePath::try_new(root!("foo/bar"))
+            .unwrap()
+            .with_file_name("baz")
+            .unwrap(),
+    );
+}
+
+#[test]
+fn test_extensions() {
+    let mut path = base(root!("foo.tar"));
+    assert!(path.add_extension("gz"));
+    assert_eq!(Path::new(root!("foo.tar.gz")), path);
+    assert!(path.add_extension(""));
+    assert_eq!(Path::new(root!("foo.tar.gz")), path);
+    assert!(path.set_extension("bz2"));
+    assert_eq!(Path::new(root!("foo.tar.bz2")), path);
+
+    let mut path = base(root!(""));
+    assert!(!path.add_extension("gz"));
+    assert!(!path.set_extension("gz"));
+    assert_eq!(Path::new(root!("")), path);
+    assert_eq!(Path::new(root!("")), path.with_extension("gz"));
+
+    assert_eq!(
+        Path::new(root!("foo.txt")),
+        BasePath::try_new(root!("foo.rs"))
+            .unwrap()
+            .with_extension("txt"),
+    );
+}
+
+#[test]
+fn test_clear() {
+    let mut path = base(root!("foo/bar"));
+    let capacity = path.capacity();
+    path.clear();
+    assert_eq!(Path::new(root!("")), path);
+    assert_eq!(capacity, path.capacity());
+
+    #[cfg(windows)]
+    {
+        #[track_caller]
+        fn test(result: &str, path: &str) {
+            let mut path = base(path);
+            path.clear();
+            assert_eq!(Path::new(result), path);
+        }
+
+        test("X:", "X:foo");
+        test(r"\\?\X:\", r"\\?\X:\foo\bar");
+        test(r"\\server\share\", r"\\server\share\foo");
+        test(r"\\server\share", r"\\server\share");
+        test(r"\\?\UNC\server\share\", r"\\?\UNC\server\share\foo");
+    }
+
+    #[cfg(not(windows))]
+    {
+        let mut path = base("foo/bar");
+        path.clear();
+        assert_eq!(Path::new(""), path);
+    }
+}
+
+#[test]
+fn test_capacity() {
+    let root = BasePath::try_new(root!("")).unwrap();
+    let mut path = BasePathBuf::with_capacity(root, 100);
+    assert_eq!(root, path);
+    assert!(path.capacity() >= 100);
+
+    path.push("foo");
+    path.shrink_to_fit();
+    assert_eq!(Path::new(root!("foo")), path);
+    path.reserve(50);
+    assert!(path.capacity() >= path.as_os_str().len() + 50);
+    path.reserve_exact(100);
+    assert!(path.capacity() >= path.as_os_str().len() + 100);
+    path.shrink_to(0);
+    assert_eq!(Path::new(root!("foo")), path);
+
+    assert_eq!(root, &*BasePathBuf::with_capacity(root, 0));
+}
+
+#[test]
+fn test_into_boxed_base_path() {
+    let path = base(root!("foo/bar"));
+    let boxed = path.clone().into_boxed_base_path();
+    assert_eq!(&*path, &*boxed);
+    assert_eq!(Some(Path::new("bar").as_os_str()), boxed.file_name());
+}
@@ -0,0 +1,86 @@
+use std::borrow::Borrow;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use normpath::BasePath;
+use normpath::BasePathBuf;
+
+const PATH: &str = if cfg!(windows) {
+    r"X:\foo\bar"
+} else {
+    "/foo/bar"
+};
+
+fn path() -> &'static BasePath {
+    BasePath::try_new(PATH).unwrap()
+}
+
+#[test]
+fn test_box() {
+    let boxed: Box<BasePath> = path().into();
+    assert_eq!(path(), &*boxed);
+    assert_eq!(boxed, boxed.clone());
+
+    let boxed: Box<BasePath> = path().to_owned().into();
+    assert_eq!(path(), &*boxed);
+    let boxed: Box<BasePath> = Cow::Borrowed(path()).into();
+    assert_eq!(path(), &*boxed);
+
+    let path_buf: BasePathBuf = boxed.into();
+    assert_eq!(path(), path_buf);
+    assert_eq!(
+        path_buf,
+        path_buf.clone().into_boxed_base_path().into_base_path_buf()
+    );
+}
+
+#[test]
+fn test_shared() {
+    let rc: Rc<BasePath> = path().into();
+    assert_eq!(path(), &*rc);
+    let rc: Rc<BasePath> = path().to_owned().into();
+    assert_eq!(path(), &*rc);
+    assert_eq!(Some(OsStr::new("bar")), rc.clone().file_name());
+
+    let arc: Arc<BasePath> = path().into();
+    assert_eq!(path(), &*arc);
+    let arc: Arc<BasePath> = path().to_owned().into();
+    assert_eq!(path(), &*arc);
+
+    let mut set = HashSet::new();
+    let _ = set.insert(arc);
+    assert!(set.contains(path()));
+    assert!(set.iter().any(|x| Borrow::<BasePath>::borrow(x) == path()));
+}
+
+#[test]
+fn test_eq() {
+    let path_buf = path().to_owned();
+    let os_str = OsStr::new(PATH);
+    let os_string = OsString::from(PATH);
+    let boxed_path: Box<Path> = Path::new(PATH).into();
+
+    assert_eq!(path(), os_str);
+    assert_eq!(os_str, path());
+    assert_eq!(*path(), os_str);
+    assert_eq!(path(), os_string);
+    assert_eq!(path(), Cow::Borrowed(os_str));
+    assert_eq!(path(), boxed_path);
+    assert_eq!(boxed_path, *path());
+
+    assert_eq!(path_buf, *os_str);
+    assert_eq!(path_buf, os_str);
+    assert_eq!(path_buf, os_string);
+    assert_eq!(os_string, path_buf);
+    assert_eq!(path_buf, Cow::<OsStr>::Owned(os_string.clone()));
+    assert_eq!(path_buf, boxed_path);
+
+    let other = OsStr::new(if cfg!(windows) { r"X:\foo" } else { "/foo" });
+    assert_ne!(path(), other);
+    assert_ne!(path_buf, other.to_owned());
+}
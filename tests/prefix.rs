@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::PrefixInfo;
+
+#[track_caller]
+fn test(result: Option<(PrefixInfo<'_>, &str)>, path: &str) {
+    let path = BasePath::try_new(path).unwrap();
+    let result = result.map(|(prefix, path)| (prefix, Path::new(path)));
+    assert_eq!(result, path.split_prefix());
+    assert_eq!(result.map(|(prefix, _)| prefix), path.prefix_info());
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_posix() {
+    test(Some((PrefixInfo::PosixRoot, "")), "/");
+    test(Some((PrefixInfo::PosixRoot, "")), "//");
+    test(Some((PrefixInfo::PosixRoot, "foo/bar")), "/foo/bar");
+    test(Some((PrefixInfo::PosixRoot, "foo/bar")), "/foo//bar/");
+    test(None, "foo/bar");
+    test(None, "./foo");
+    test(None, "");
+
+    assert!(!PrefixInfo::PosixRoot.is_verbatim());
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows() {
+    use std::ffi::OsStr;
+
+    let server = OsStr::new("server");
+    let share = OsStr::new("share");
+
+    test(Some((PrefixInfo::Disk(b'C'), "")), r"C:\");
+    test(Some((PrefixInfo::Disk(b'C'), "")), r"C:");
+    test(Some((PrefixInfo::Disk(b'c'), r"foo\bar")), r"c:\foo\bar");
+    test(Some((PrefixInfo::Disk(b'C'), r"foo\bar")), r"C:foo\bar");
+    test(Some((PrefixInfo::VerbatimDisk(b'C'), "foo")), r"\\?\C:\foo");
+    test(
+        Some((PrefixInfo::Unc(server, share), "foo")),
+        r"\\server\share\foo",
+    );
+    test(
+        Some((PrefixInfo::Unc(server, share), "")),
+        r"\\server\share",
+    );
+    test(
+        Some((PrefixInfo::VerbatimUnc(server, share), "foo")),
+        r"\\?\UNC\server\share\foo",
+    );
+    test(
+        Some((PrefixInfo::DeviceNs(OsStr::new("COM1")), "")),
+        r"\\.\COM1",
+    );
+    test(
+        Some((PrefixInfo::Verbatim(OsStr::new("foo")), "bar")),
+        r"\\?\foo\bar",
+    );
+
+    assert!(PrefixInfo::VerbatimDisk(b'C').is_verbatim());
+    assert!(!PrefixInfo::Disk(b'C').is_verbatim());
+}
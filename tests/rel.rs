@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::RelPath;
+use normpath::RelPathBuf;
+
+fn base() -> &'static BasePath {
+    let base = if cfg!(windows) { r"X:\foo" } else { "/foo" };
+    BasePath::try_new(base).unwrap()
+}
+
+#[test]
+fn test_valid() {
+    for path in [
+        "",
+        ".",
+        "foo",
+        "foo/bar",
+        "./foo/./bar/",
+        "foo..",
+        "...",
+        "~",
+    ] {
+        assert_eq!(Path::new(path), RelPath::try_new(path).unwrap().as_path(),);
+        assert_eq!(
+            Path::new(path),
+            RelPathBuf::try_new(path).unwrap().as_path(),
+        );
+    }
+}
+
+#[test]
+fn test_invalid() {
+    let mut paths = vec!["..", "../foo", "foo/..", "foo/../bar", "/", "/foo"];
+    if cfg!(windows) {
+        paths.extend([
+            r"\foo",
+            r"foo\..\bar",
+            "C:",
+            "C:foo",
+            r"\\server\share",
+        ]);
+    }
+    for path in paths {
+        assert!(RelPath::try_new(path).is_err(), "{:?}", path);
+        assert_eq!(
+            Path::new(path),
+            RelPathBuf::try_new(path).unwrap_err().as_path(),
+        );
+    }
+}
+
+#[test]
+fn test_join_rel() {
+    #[track_caller]
+    fn test(path: &str) {
+        let rel_path = RelPath::try_new(path).unwrap();
+        let result = base().join_rel(rel_path);
+        assert_eq!(Path::new(base()).join(path), result);
+        assert!(result.starts_with(base()));
+        assert_eq!(
+            Some(Path::new(path)),
+            result.strip_base(base()).map(RelPath::as_path),
+        );
+    }
+
+    test("bar");
+    test("bar/baz");
+    test("bar/baz/");
+    assert_eq!(base(), base().join_rel(RelPath::try_new("").unwrap()));
+}
+
+#[test]
+fn test_strip_base() {
+    let path = base().join("bar/../baz");
+    assert_eq!(None, path.strip_base(base()));
+
+    let path = base().join("bar");
+    assert_eq!(None, base().strip_base(&path));
+    assert_eq!(
+        Some(Path::new("")),
+        base().strip_base(base()).map(RelPath::as_path),
+    );
+
+    let other = if cfg!(windows) {
+        r"X:\foobar"
+    } else {
+        "/foobar"
+    };
+    assert_eq!(None, BasePath::try_new(other).unwrap().strip_base(base()));
+}
+
+#[test]
+fn test_parent() {
+    let mut path = RelPathBuf::try_new("foo/bar").unwrap();
+    assert_eq!(Some(Path::new("foo")), path.parent().map(RelPath::as_path));
+
+    assert!(path.pop());
+    assert_eq!(Path::new("foo"), path.as_path());
+    assert!(path.pop());
+    assert_eq!(Path::new(""), path.as_path());
+    assert!(path.is_empty());
+    assert!(!path.pop());
+    assert_eq!(None, path.parent());
+}
+
+#[test]
+fn test_push() {
+    let mut path = RelPathBuf::new();
+    path.push(RelPath::try_new("").unwrap());
+    assert_eq!(RelPathBuf::new(), path);
+
+    path.push(RelPath::try_new("foo").unwrap());
+    path.push(RelPath::try_new("").unwrap());
+    path.push(RelPath::try_new("bar").unwrap());
+    assert_eq!(Path::new("foo").join("bar"), path.as_path());
+    assert_eq!(
+        path,
+        RelPath::try_new("foo")
+            .unwrap()
+            .join(RelPath::try_new("bar").unwrap()),
+    );
+}
@@ -0,0 +1,285 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::Platform;
+use normpath::SanitizeOptions;
+
+#[track_caller]
+fn test_with(result: &str, name: &str, options: SanitizeOptions) {
+    assert_eq!(
+        OsStr::new(result),
+        normpath::sanitize_file_name(OsStr::new(name), options),
+    );
+}
+
+#[track_caller]
+fn test(result: &str, name: &str) {
+    test_with(result, name, SanitizeOptions::new());
+}
+
+#[test]
+fn test_valid() {
+    for name in ["foo", "foo.txt", ".foo", "foo bar", "\u{E9}t\u{E9}", "..."] {
+        test_with(
+            name,
+            name,
+            SanitizeOptions::new().platform(Platform::Linux),
+        );
+    }
+    for name in ["foo", "foo.txt", ".foo", "foo bar", "\u{E9}t\u{E9}"] {
+        test(name, name);
+    }
+}
+
+#[test]
+fn test_separators() {
+    test("foo_bar", "foo/bar");
+    test("foo_bar", r"foo\bar");
+    test("_.._.._etc_passwd", "/../../etc/passwd");
+    test("__", "..");
+    test("_", ".");
+    test("_", "");
+
+    let options = SanitizeOptions::new().platform(Platform::Linux);
+    test_with("foo_bar", r"foo\bar", options.clone());
+    test_with("__", "..", options.clone());
+    test_with("_", ".", options);
+}
+
+#[test]
+fn test_characters() {
+    test("a_b_c_d_e_f_g", r#"a<b>c:d"e|f?g"#);
+    test("foo_", "foo*");
+    test("foo_bar_", "foo\0bar\n");
+    test("foo_bar", "foo\u{7F}bar");
+    test("foo_bar", "foo\u{85}bar");
+
+    let options = SanitizeOptions::new().platform(Platform::Linux);
+    test_with(r#"a<b>c:d"e|f?g"#, r#"a<b>c:d"e|f?g"#, options.clone());
+    test_with("foo_bar_", "foo\0bar\n", options.clone());
+
+    test_with(
+        "foo-bar",
+        "foo/bar",
+        SanitizeOptions::new().replacement('-'),
+    );
+    test_with(
+        "foo\u{2044}bar",
+        "foo/bar",
+        SanitizeOptions::new().replacement('\u{2044}'),
+    );
+}
+
+#[test]
+fn test_windows() {
+    test("foo", "foo.");
+    test("foo", "foo. . ");
+    test("_", ". .");
+    test("CO_", "CON");
+    test("nu_.txt", "nul.txt");
+    test("Au_ .tar.gz", "Aux .tar.gz");
+    test("COM_", "COM\u{B9}");
+    test("CONIN_", "CONIN$");
+    test("CO_", "CON.");
+    test("LPT_", "LPT1 ");
+    test("CONSOLE", "CONSOLE");
+
+    let options = SanitizeOptions::new().platform(Platform::MacOs);
+    test_with("foo.", "foo.", options.clone());
+    test_with("CON", "CON", options);
+}
+
+#[test]
+fn test_truncation() {
+    #[track_caller]
+    fn test(result: &str, name: &str, max_len: usize) {
+        test_with(result, name, SanitizeOptions::new().max_len(max_len));
+    }
+
+    let name = "a".repeat(300);
+    test(&name[..255], &name, 255);
+    test(
+        &format!("{}.txt", &name[..251]),
+        &format!("{}.txt", name),
+        255,
+    );
+    test("abcdefg", "abcdefg", 7);
+    test("abcdef.gz", "abcdef.tar.gz", 9);
+    test("abcd.gz", "abcdef.tar.gz", 7);
+    test("abcde.gz", "abcdef.gz", 8);
+    test("a.txt", "abcdef.txt", 5);
+    test(".txt", ".txt", 4);
+    test(".tx", ".txt", 3);
+    test("abc", "abc.verylongextension", 3);
+    test("_", "abc", 0);
+
+    // Characters are never split.
+    test("\u{E9}", "\u{E9}\u{E9}", 3);
+    test("\u{E9}.txt", "\u{E9}\u{E9}.txt", 7);
+    test("\u{E9}\u{E9}", "\u{E9}\u{E9}.txt", 4);
+    test("_", "\u{E9}\u{E9}", 1);
+
+    // The result of truncation is sanitized again.
+    test("ab", "ab .txt", 3);
+    test("CO_.txt", "CONX.txt", 7);
+
+    // Truncation can create special names.
+    for platform in [Platform::Linux, Platform::MacOs, Platform::Portable] {
+        test_with(
+            "__",
+            "..abc",
+            SanitizeOptions::new().platform(platform).max_len(2),
+        );
+        test_with(
+            "_",
+            ".abc",
+            SanitizeOptions::new().platform(platform).max_len(1),
+        );
+    }
+    test_with(
+        "\u{E9}",
+        "..abc",
+        SanitizeOptions::new()
+            .platform(Platform::Linux)
+            .replacement('\u{E9}')
+            .max_len(2),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_encoding() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let name = OsStr::from_bytes(b"foo\x80\x81bar");
+    assert_eq!(
+        OsStr::new("foo__bar"),
+        normpath::sanitize_file_name(name, SanitizeOptions::new()),
+    );
+    assert_eq!(
+        name,
+        normpath::sanitize_file_name(
+            name,
+            SanitizeOptions::new().platform(Platform::Linux),
+        ),
+    );
+
+    // Invalid sequences can be split at any byte.
+    let options = SanitizeOptions::new().platform(Platform::Linux);
+    let name = [0x80; 300];
+    assert_eq!(
+        OsStr::from_bytes(&name[..255]),
+        normpath::sanitize_file_name(
+            OsStr::from_bytes(&name),
+            options.clone()
+        ),
+    );
+    assert_eq!(
+        OsStr::from_bytes(b"\x80"),
+        normpath::sanitize_file_name(
+            OsStr::from_bytes(b"\x80\xC3\xA9"),
+            options.clone().max_len(2),
+        ),
+    );
+
+    // Sequences with the structure of a character are never split.
+    let name = OsStr::from_bytes(b"foo\xED\xA0\x80bar");
+    assert_eq!(
+        OsStr::new("foo_bar"),
+        normpath::sanitize_file_name(name, SanitizeOptions::new()),
+    );
+    assert_eq!(
+        OsStr::new("foo"),
+        normpath::sanitize_file_name(name, options.max_len(5)),
+    );
+}
+
+#[cfg(windows)]
+#[test]
+fn test_surrogates() {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::ffi::OsStringExt;
+
+    let name = OsString::from_wide(&[
+        0x66, 0xD800, 0x6F, 0xDC00, 0xE9, 0x2E, 0xD83D, 0x74,
+    ]);
+    assert_eq!(
+        OsStr::new("f_o_\u{E9}._t"),
+        normpath::sanitize_file_name(&name, SanitizeOptions::new()),
+    );
+
+    assert_eq!(
+        name,
+        normpath::sanitize_file_name(
+            &name,
+            SanitizeOptions::new().platform(Platform::Windows),
+        ),
+    );
+
+    // Surrogates are never split.
+    for max_len in 0..=name.len() {
+        for platform in
+            [Platform::Linux, Platform::Portable, Platform::Windows]
+        {
+            let result = normpath::sanitize_file_name(
+                &name,
+                SanitizeOptions::new().platform(platform).max_len(max_len),
+            );
+            assert!(result.len() <= max_len.max(1), "{:?}", result);
+            let wide: Vec<_> = result.encode_wide().collect();
+            assert_eq!(result, OsString::from_wide(&wide));
+        }
+    }
+}
+
+#[should_panic = "invalid replacement character: '/'"]
+#[test]
+fn test_invalid_replacement() {
+    let _ = SanitizeOptions::new().replacement('/');
+}
+
+#[should_panic = "invalid replacement character: '.'"]
+#[test]
+fn test_dot_replacement() {
+    let _ = SanitizeOptions::new().replacement('.');
+}
+
+#[test]
+fn test_join() {
+    let base = if cfg!(windows) { r"X:\foo" } else { "/foo" };
+    let base = BasePath::try_new(base).unwrap();
+    for name in [
+        "..",
+        ".",
+        "",
+        "/",
+        "/etc/passwd",
+        r"..\..\bar",
+        "C:",
+        r"C:\bar",
+        r"\\server\share",
+        "bar\0",
+    ] {
+        for platform in [Platform::Linux, Platform::Portable] {
+            let path = base.join_sanitized(
+                name,
+                SanitizeOptions::new().platform(platform),
+            );
+            assert_eq!(Ok(Some(base)), path.parent(), "{:?}", name);
+            assert_ne!(Some(OsStr::new("..")), path.file_name());
+        }
+    }
+
+    let path = base.join_sanitized(
+        "..abc",
+        SanitizeOptions::new().platform(Platform::Linux).max_len(2),
+    );
+    assert_eq!(Ok(Some(base)), path.parent());
+
+    assert_eq!(
+        Path::new(base).join("bar.txt"),
+        base.join_sanitized("bar.txt", SanitizeOptions::new()),
+    );
+}
@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use normpath::BasePath;
+use normpath::BasePathBuf;
+use normpath::PosixStyle;
+use normpath::PosixTranslator;
+
+#[track_caller]
+fn test_to(result: Option<&str>, path: &str, translator: &PosixTranslator) {
+    assert_eq!(
+        result.map(Path::new),
+        BasePath::try_new(path)
+            .unwrap()
+            .to_posix_with(translator)
+            .as_deref(),
+    );
+}
+
+#[track_caller]
+fn test_from(result: Option<&str>, path: &str, translator: &PosixTranslator) {
+    assert_eq!(
+        result.map(Path::new),
+        BasePathBuf::from_posix_with(path, translator)
+            .as_ref()
+            .map(|x| x.as_path()),
+    );
+}
+
+#[track_caller]
+fn test(windows_path: &str, posix_path: &str, translator: &PosixTranslator) {
+    test_to(Some(posix_path), windows_path, translator);
+    test_from(Some(windows_path), posix_path, translator);
+}
+
+#[test]
+fn test_drives() {
+    let wsl = PosixTranslator::new(PosixStyle::Wsl);
+    let msys = PosixTranslator::new(PosixStyle::Msys);
+    let cygwin = PosixTranslator::new(PosixStyle::Cygwin);
+
+    test(r"C:\", "/mnt/c", &wsl);
+    test(r"C:\Users\foo", "/mnt/c/Users/foo", &wsl);
+    test(r"C:\", "/c", &msys);
+    test(r"C:\Users\foo", "/c/Users/foo", &msys);
+    test(r"C:\", "/cygdrive/c", &cygwin);
+    test(r"C:\Users\foo", "/cygdrive/c/Users/foo", &cygwin);
+
+    test_to(Some("/mnt/x/foo"), r"x:/foo/", &wsl);
+    test_to(Some("/mnt/x/foo"), r"\\?\X:\foo", &wsl);
+    test_from(Some(r"X:\foo"), "/mnt/X//foo/", &wsl);
+
+    let translator = PosixTranslator::new(PosixStyle::Wsl).mount_root("/");
+    test(r"X:\foo\bar", "/x/foo/bar", &translator);
+    let translator =
+        PosixTranslator::new(PosixStyle::Cygwin).mount_root("/drives/");
+    test(r"X:\foo\bar", "/drives/x/foo/bar", &translator);
+}
+
+#[test]
+fn test_parents() {
+    let wsl = PosixTranslator::new(PosixStyle::Wsl);
+
+    test_to(Some("/mnt/c/bar"), r"C:\foo\..\.\bar", &wsl);
+    test_to(Some("/mnt/c"), r"C:\..\..", &wsl);
+    test_to(None, r"\\?\C:\foo\..\bar", &wsl);
+
+    test_from(Some(r"C:\bar"), "/mnt/c/foo/../bar", &wsl);
+    test_from(Some(r"C:\bar"), "/mnt/foo/../c/bar", &wsl);
+}
+
+#[test]
+fn test_unc() {
+    let wsl = PosixTranslator::new(PosixStyle::Wsl);
+    let msys = PosixTranslator::new(PosixStyle::Msys);
+    let cygwin = PosixTranslator::new(PosixStyle::Cygwin);
+
+    test(r"\\server\share\foo", "//server/share/foo", &msys);
+    test(r"\\server\share", "//server/share", &cygwin);
+    test_to(
+        Some("//server/share/foo"),
+        r"\\?\UNC\server\share\foo",
+        &msys,
+    );
+    test_from(Some(r"C:\foo"), "///c/foo", &msys);
+    test_from(None, "//server", &msys);
+
+    test_to(Some("/home/foo"), r"\\wsl$\Ubuntu\home\foo", &wsl);
+    test_to(Some("/"), r"\\wsl.localhost\Ubuntu", &wsl);
+    test_to(None, r"\\server\share\foo", &wsl);
+    test_from(None, "/home/foo", &wsl);
+    test_from(None, "//server/share", &wsl);
+
+    let ubuntu = PosixTranslator::new(PosixStyle::Wsl).wsl_distro("Ubuntu");
+    test(r"\\wsl$\Ubuntu\home\foo", "/home/foo", &ubuntu);
+    test(r"\\wsl$\Ubuntu", "/", &ubuntu);
+    test(r"C:\foo", "/mnt/c/foo", &ubuntu);
+    test_to(None, r"\\wsl$\Debian\home\foo", &ubuntu);
+    test_from(Some(r"\\wsl$\Ubuntu\mnt\foo"), "/mnt/foo", &ubuntu);
+
+    let translator =
+        PosixTranslator::new(PosixStyle::Msys).wsl_distro("Ubuntu");
+    test(r"\\wsl$\Ubuntu\home", "//wsl$/Ubuntu/home", &translator);
+}
+
+#[test]
+fn test_untranslatable() {
+    let wsl = PosixTranslator::new(PosixStyle::Wsl);
+
+    test_to(None, r"C:foo", &wsl);
+    test_to(None, r"\\.\COM1", &wsl);
+    test_to(
+        None,
+        r"\\?\Volume{00000000-0000-0000-0000-000000000000}",
+        &wsl,
+    );
+    if cfg!(not(windows)) {
+        test_to(None, "/mnt/c/foo", &wsl);
+        test_to(None, "foo", &wsl);
+    }
+
+    test_from(None, "mnt/c/foo", &wsl);
+    test_from(None, r"/mnt/c/foo\bar", &wsl);
+}
+
+#[test]
+fn test_defaults() {
+    #[track_caller]
+    fn test(
+        windows_path: &str,
+        posix_path: &'static str,
+        to_posix: fn(&BasePath) -> Option<std::path::PathBuf>,
+        from_posix: fn(&'static str) -> Option<BasePathBuf>,
+    ) {
+        let path = BasePath::try_new(windows_path).unwrap();
+        assert_eq!(Some(Path::new(posix_path)), to_posix(path).as_deref());
+        assert_eq!(
+            Some(Path::new(windows_path)),
+            from_posix(posix_path).as_ref().map(|x| x.as_path()),
+        );
+    }
+
+    test(
+        r"C:\foo",
+        "/mnt/c/foo",
+        BasePath::to_wsl,
+        BasePathBuf::from_wsl,
+    );
+    test(
+        r"C:\foo",
+        "/c/foo",
+        BasePath::to_msys,
+        BasePathBuf::from_msys,
+    );
+    test(
+        r"C:\foo",
+        "/cygdrive/c/foo",
+        BasePath::to_cygwin,
+        BasePathBuf::from_cygwin,
+    );
+}
@@ -0,0 +1,182 @@
+use std::ffi::OsStr;
+
+use normpath::error::InvalidComponentKind;
+use normpath::BasePath;
+use normpath::Platform;
+
+const PLATFORMS: [Platform; 4] = [
+    Platform::Linux,
+    Platform::MacOs,
+    Platform::Portable,
+    Platform::Windows,
+];
+
+// Windows paths have an additional prefix component.
+const OFFSET: usize = if cfg!(windows) { 1 } else { 0 };
+
+fn join(name: &str) -> String {
+    if cfg!(windows) {
+        format!(r"X:\foo\{}", name)
+    } else {
+        format!("/foo/{}", name)
+    }
+}
+
+#[track_caller]
+fn test(result: &[InvalidComponentKind], name: &str, platform: Platform) {
+    let path = join(name);
+    let path = BasePath::try_new(&path).unwrap();
+    match path.validate_for(platform) {
+        Ok(()) => assert_eq!(result, &[]),
+        Err(error) => {
+            for component in error.components() {
+                assert_eq!(2 + OFFSET, component.index());
+                assert_eq!(OsStr::new(name), component.name());
+            }
+            assert_eq!(
+                result,
+                &*error
+                    .components()
+                    .iter()
+                    .map(|x| x.kind())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
+
+#[track_caller]
+fn test_windows(result: &[InvalidComponentKind], name: &str) {
+    test(&[], name, Platform::Linux);
+    test(&[], name, Platform::MacOs);
+    test(result, name, Platform::Portable);
+    test(result, name, Platform::Windows);
+}
+
+#[test]
+fn test_valid() {
+    for name in ["foo", "foo.txt", ".foo", "foo bar", "\u{7F}", "CONSOLE"] {
+        for platform in PLATFORMS {
+            test(&[], name, platform);
+        }
+    }
+}
+
+#[test]
+fn test_reserved_names() {
+    for name in ["CON", "nul", "Aux.txt", "COM1.tar.gz", "lpt9", "COM\u{B9}"] {
+        test_windows(&[InvalidComponentKind::ReservedName], name);
+    }
+    test_windows(
+        &[
+            InvalidComponentKind::ReservedName,
+            InvalidComponentKind::TrailingDotOrSpace,
+        ],
+        "prn ",
+    );
+}
+
+#[test]
+fn test_characters() {
+    for ch in ['"', '*', ':', '<', '>', '?', '|'] {
+        test_windows(
+            &[InvalidComponentKind::ForbiddenCharacter(ch)],
+            &format!("foo{}bar{}", ch, ch),
+        );
+    }
+    if cfg!(not(windows)) {
+        test_windows(
+            &[InvalidComponentKind::ForbiddenCharacter('\\')],
+            r"foo\bar",
+        );
+    }
+
+    test_windows(
+        &[InvalidComponentKind::ControlCharacter('\n')],
+        "foo\nbar\t",
+    );
+    for platform in PLATFORMS {
+        test(
+            &[InvalidComponentKind::ControlCharacter('\0')],
+            "foo\0bar",
+            platform,
+        );
+    }
+}
+
+#[test]
+fn test_trailing_characters() {
+    for name in ["foo.", "foo ", "foo. .", "..."] {
+        test_windows(&[InvalidComponentKind::TrailingDotOrSpace], name);
+    }
+}
+
+#[test]
+fn test_length() {
+    let name = "a".repeat(255);
+    for platform in PLATFORMS {
+        test(&[], &name, platform);
+    }
+    let name = "a".repeat(256);
+    for platform in PLATFORMS {
+        test(&[InvalidComponentKind::TooLong], &name, platform);
+    }
+
+    // This name has 255 UTF-16 code units but more than 255 bytes.
+    let name = "\u{E9}".repeat(255);
+    test(&[], &name, Platform::Windows);
+    test(&[InvalidComponentKind::TooLong], &name, Platform::Linux);
+    test(&[InvalidComponentKind::TooLong], &name, Platform::MacOs);
+    test(&[InvalidComponentKind::TooLong], &name, Platform::Portable);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_encoding() {
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    let path = Path::new(OsStr::from_bytes(b"/foo/\x80/bar"));
+    let path = BasePath::try_new(path).unwrap();
+    assert_eq!(Ok(()), path.validate_for(Platform::Linux));
+    assert_eq!(Ok(()), path.validate_for(Platform::Windows));
+    for platform in [Platform::MacOs, Platform::Portable] {
+        let error = path.validate_for(platform).unwrap_err();
+        let components = error.components();
+        assert_eq!(1, components.len());
+        assert_eq!(2 + OFFSET, components[0].index());
+        assert_eq!(
+            InvalidComponentKind::InvalidEncoding,
+            components[0].kind()
+        );
+    }
+}
+
+#[test]
+fn test_multiple_components() {
+    let path = if cfg!(windows) {
+        r"X:\CON\foo\bar.\..\baz?"
+    } else {
+        "/CON/foo/bar./../baz?"
+    };
+    let error = BasePath::try_new(path)
+        .unwrap()
+        .validate_for(Platform::Windows)
+        .unwrap_err();
+    assert_eq!(
+        [
+            (1, InvalidComponentKind::ReservedName),
+            (3, InvalidComponentKind::TrailingDotOrSpace),
+            (5, InvalidComponentKind::ForbiddenCharacter('?')),
+        ],
+        &*error
+            .components()
+            .iter()
+            .map(|x| (x.index() - OFFSET, x.kind()))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        r#"path has invalid components: "CON" is a reserved device name, "bar." ends with a period or space, "baz?" contains a forbidden character: '?'"#,
+        error.to_string(),
+    );
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use normpath::BasePath;
+
+#[track_caller]
+fn test_to(result: Option<&str>, path: &str) {
+    assert_eq!(
+        result.map(Path::new),
+        BasePath::try_new(path)
+            .unwrap()
+            .to_verbatim()
+            .as_ref()
+            .map(|x| x.as_path()),
+    );
+}
+
+#[track_caller]
+fn test_simplified(result: &str, path: &str) {
+    assert_eq!(
+        Path::new(result),
+        BasePath::try_new(path).unwrap().simplified().as_path(),
+    );
+}
+
+#[track_caller]
+fn test(path: &str, verbatim_path: &str) {
+    test_to(Some(verbatim_path), path);
+    test_simplified(path, verbatim_path);
+}
+
+#[test]
+fn test_drive() {
+    test(r"C:\", r"\\?\C:\");
+    test(r"C:\foo\bar", r"\\?\C:\foo\bar");
+    test(r"x:\foo\bar\", r"\\?\x:\foo\bar\");
+
+    test_to(Some(r"\\?\C:\foo\bar"), r"C:/foo//bar");
+    test_to(Some(r"\\?\C:\foo\baz"), r"C:\foo\.\bar\..\baz");
+    test_to(Some(r"\\?\C:\"), r"C:\..\..");
+    test_to(None, r"C:");
+    test_to(None, r"C:foo");
+}
+
+#[test]
+fn test_unc() {
+    test(r"\\server\share", r"\\?\UNC\server\share");
+    test(r"\\server\share\foo", r"\\?\UNC\server\share\foo");
+
+    test_to(Some(r"\\?\UNC\server\share"), r"//server/share/foo/..");
+    test_to(Some(r"\\?\UNC\server\share\foo"), r"\\server\share\..\foo");
+    test_simplified(r"\\?\UNC\server\share.", r"\\?\UNC\server\share.");
+}
+
+#[test]
+fn test_device() {
+    test_to(Some(r"\\?\COM1"), r"\\.\COM1");
+    test_to(Some(r"\\?\pipe\foo"), r"\\.\pipe\bar\..\foo");
+    test_simplified(r"\\?\COM1", r"\\?\COM1");
+    test_simplified(r"\\?\C:", r"\\?\C:");
+}
+
+#[test]
+fn test_verbatim() {
+    for path in [r"\\?\C:\foo\..\bar", r"\\?\UNC\server\share\.", r"\\?\foo"] {
+        test_to(Some(path), path);
+    }
+}
+
+#[test]
+fn test_trailing_characters() {
+    test_to(Some(r"\\?\C:\foo\bar"), r"C:\foo.\bar");
+    test_to(Some(r"\\?\C:\foo..\bar"), r"C:\foo..\bar");
+    test_to(Some(r"\\?\C:\foo\bar"), r"C:\foo\bar. . ");
+    test_to(Some(r"\\?\C:\foo\bar. \"), r"C:\foo\bar. \");
+    test_to(Some(r"\\?\C:\foo"), r"C:\foo\...");
+
+    for path in [
+        r"\\?\C:\foo.",
+        r"\\?\C:\foo \bar",
+        r"\\?\C:\foo\..\bar",
+        r"\\?\C:\foo\.",
+        r"\\?\C:\foo\\bar",
+        r"\\?\C:\foo/bar",
+    ] {
+        test_simplified(path, path);
+    }
+}
+
+#[test]
+fn test_reserved_names() {
+    for name in [
+        "CON",
+        "nul",
+        "Aux.txt",
+        "prn ",
+        "COM1",
+        "lpt9.tar.gz",
+        "COM¹",
+        "conin$",
+        "CONOUT$",
+    ] {
+        test_to(None, &format!(r"C:\foo\{}", name));
+        let path = format!(r"\\?\C:\{}\foo", name);
+        test_simplified(&path, &path);
+    }
+
+    test_to(Some(r"\\?\C:\CON\foo"), r"C:\CON\foo");
+    for name in ["CONSOLE", "COM0", "LPT10", "NULL", "foo.nul"] {
+        test(
+            &format!(r"C:\foo\{}", name),
+            &format!(r"\\?\C:\foo\{}", name),
+        );
+    }
+}
+
+#[test]
+fn test_max_path() {
+    let component = "a".repeat(250);
+    test(
+        &format!(r"C:\{}", component),
+        &format!(r"\\?\C:\{}", component),
+    );
+
+    let path = format!(r"\\?\C:\{}\{}", component, component);
+    test_simplified(&path, &path);
+    test_to(Some(&path), &path[4..]);
+
+    let path = format!(r"\\?\C:\{}", "\u{10000}".repeat(129));
+    test_simplified(&path, &path);
+    let path = format!(r"\\?\C:\{}", "\u{10000}".repeat(128));
+    test_simplified(&path[4..], &path);
+}